@@ -9,6 +9,10 @@ pub struct Core {
     pub nodes: AHashMap<u64, Vec<Node>>,
     // pub nodes : BTreeMap<u64,BTreeMap<u64,Node>>,
     pub machines: AHashMap<u64, Machine>,
+    pub divergences: AHashMap<u64, Vec<Arc<Divergence>>>,
+    pub node_info: AHashMap<u64, Arc<NodeInfo>>,
+    pub states: AHashMap<u64, NodeState>,
+    pub health: AHashMap<u64, Arc<Health>>,
 }
 
 impl Core {
@@ -43,9 +47,53 @@ impl Core {
             manager,
             nodes: Default::default(),
             machines: Default::default(),
+            divergences: Default::default(),
+            node_info: Default::default(),
+            states: Default::default(),
+            health: Default::default(),
         })
     }
 
+    /// Replace the current state with the given snapshot.
+    pub fn handle_snapshot(&mut self, snapshot: &Snapshot) {
+        self.nodes.clear();
+        self.machines.clear();
+        self.node_info.clear();
+        self.divergences.clear();
+        self.states.clear();
+        self.health.clear();
+
+        for connection in snapshot.connections.iter() {
+            self.handle_update(&Update::NodeAdded {
+                node: connection.node.clone(),
+            });
+            self.handle_update(&Update::State {
+                uid: connection.node.uid,
+                connected: connection.connected,
+                online: connection.online,
+                synced: connection.synced,
+                delegate: connection.delegates.first().copied(),
+                since: connection.since,
+            });
+            if let Some(caps) = connection.caps.as_ref() {
+                self.handle_update(&Update::Caps {
+                    uid: connection.node.uid,
+                    caps: caps.clone(),
+                });
+            }
+            if let Some(status) = connection.status.as_ref() {
+                self.handle_update(&Update::Status {
+                    status: status.clone(),
+                });
+            }
+            if let Some(health) = connection.health.as_ref() {
+                self.handle_update(&Update::Health {
+                    health: health.clone(),
+                });
+            }
+        }
+    }
+
     pub fn handle_update(&mut self, update: &Update) {
         match update {
             Update::Status { status } => {
@@ -89,6 +137,65 @@ impl Core {
                 // let node = nodes_by_sid.entry(*uid).or_default();
                 // machine.set_caps(caps.clone());
             }
+            Update::Divergence { divergence } => {
+                let divergences = self.divergences.entry(divergence.uid).or_default();
+                divergences.retain(|d| d.kind != divergence.kind);
+                divergences.push(divergence.clone());
+            }
+            Update::Convergence { uid, kind } => {
+                if let hash_map::Entry::Occupied(mut divergences) = self.divergences.entry(*uid) {
+                    divergences.get_mut().retain(|d| d.kind != *kind);
+                    if divergences.get().is_empty() {
+                        divergences.remove();
+                    }
+                }
+            }
+            Update::NodeAdded { node } => {
+                self.node_info.insert(node.uid, node.clone());
+            }
+            Update::NodeRemoved { uid } => {
+                self.nodes.retain(|_, nodes| {
+                    nodes.retain(|node| node.uid() != *uid);
+                    !nodes.is_empty()
+                });
+                self.node_info.remove(uid);
+                self.divergences.remove(uid);
+                self.states.remove(uid);
+                self.health.remove(uid);
+            }
+            Update::MachineRemoved { sid } => {
+                if let Some(nodes) = self.nodes.remove(sid) {
+                    for node in nodes {
+                        self.node_info.remove(&node.uid());
+                        self.divergences.remove(&node.uid());
+                        self.states.remove(&node.uid());
+                        self.health.remove(&node.uid());
+                    }
+                }
+                self.machines.remove(sid);
+            }
+            Update::State {
+                uid,
+                connected,
+                online,
+                synced,
+                delegate,
+                since,
+            } => {
+                self.states.insert(
+                    *uid,
+                    NodeState {
+                        connected: *connected,
+                        online: *online,
+                        synced: *synced,
+                        delegate: *delegate,
+                        since: *since,
+                    },
+                );
+            }
+            Update::Health { health } => {
+                self.health.insert(health.uid, health.clone());
+            }
         }
     }
 }
@@ -121,6 +228,9 @@ impl App for Core {
                     Event::Update { update } => {
                         self.handle_update(update);
                     }
+                    Event::Snapshot { snapshot } => {
+                        self.handle_snapshot(snapshot);
+                    }
                 }
             }
             RuntimeEvent::Exit => {
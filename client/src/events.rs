@@ -2,4 +2,5 @@ use crate::imports::*;
 
 pub enum Event {
     Update { update: Update },
+    Snapshot { snapshot: Arc<Snapshot> },
 }
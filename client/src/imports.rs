@@ -34,7 +34,7 @@ pub use std::collections::{BTreeMap, VecDeque};
 // pub use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
 // pub use std::sync::OnceLock;
 pub use std::sync::{Arc, Mutex, MutexGuard, RwLock};
-// pub use std::time::Duration;
+pub use std::time::Duration;
 
 // pub use web_sys::VisibilityState;
 // pub use workflow_core::abortable::Abortable;
@@ -42,7 +42,7 @@ pub use workflow_core::channel::{oneshot, Channel, Receiver, Sender};
 // pub use workflow_core::enums::Describe;
 // pub use workflow_core::extensions::is_not_empty::*;
 // pub use workflow_core::task;
-pub use workflow_core::time::Instant;
+pub use workflow_core::time::{unixtime_as_millis_f64, Instant};
 // pub use workflow_dom::utils::*;
 // pub use workflow_http as http;
 // pub use workflow_i18n::prelude::*;
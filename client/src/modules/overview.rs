@@ -83,13 +83,19 @@ impl ModuleT for Overview {
                         // ui.label(format!("System ID: {}", sid));
                             nodes.iter().for_each(|node| {
 
-                                let node_caption = RichText::new(node.get_caption())
+                                let divergences = core.divergences.get(&node.uid());
+
+                                let mut node_caption = RichText::new(node.get_caption())
                                     // .size(font_size)
                                     .family(FontFamily::Name("noto_sans_mono_light".into()))
                                     // .family(FontFamily::Name(MNEMONIC_FONT.into()))
                                     .family(FontFamily::Monospace)
                                     // .color(egui::Color32::WHITE)
                                     ;
+
+                                if divergences.is_some() {
+                                    node_caption = node_caption.color(egui::Color32::LIGHT_RED);
+                                }
         
 
                                 // ui.collapsing(format!("Node {uid:016x}"), |ui| {
@@ -102,6 +108,22 @@ impl ModuleT for Overview {
                                             ui.set_max_width(400.0);
                                             ui.set_min_width(400.0);
 
+                                            if let Some(state) = core.states.get(&node.uid()) {
+                                                let secs = state.duration().as_secs();
+                                                ui.label(format!("State: {} for {}h {:02}m {:02}s", state.name(), secs / 3600, secs / 60 % 60, secs % 60));
+                                            }
+
+                                            if let Some(divergences) = divergences {
+                                                divergences.iter().for_each(|divergence| {
+                                                    let text = match divergence.kind {
+                                                        DivergenceKind::PruningPoint => "pruning point differs from the network majority".to_string(),
+                                                        DivergenceKind::DaaLag => format!("DAA score lags the network by {}", divergence.value.separated_string()),
+                                                        DivergenceKind::SinkStall => format!("sink stalled for {} sec", divergence.value / 1000),
+                                                    };
+                                                    ui.colored_label(egui::Color32::LIGHT_RED, format!("Divergence: {text}"));
+                                                });
+                                            }
+
                                             // ui.horizontal_wrapped(|ui|{
 
                                                 match node.status() {
@@ -126,7 +148,31 @@ impl ModuleT for Overview {
                                                         });
                                                             
                                                     }
-                                                    Status::Sparkle(_status) => {
+                                                    Status::Sparkle(status) => {
+
+                                                        let metrics = [
+                                                            ("Synced", if status.is_synced { "yes".to_string() } else { "no".to_string() }),
+                                                            ("Borsh Connections", status.borsh_live_connections.separated_string()),
+                                                            ("JSON Connections", status.json_live_connections.separated_string()),
+                                                            ("Active Peers", status.active_peers.separated_string()),
+                                                            ("CPU Cores", status.core_num.to_string()),
+                                                            ("CPU Usage", format!("{:1.2}%", status.cpu_usage * 100.0)),
+                                                            ("Resident Memory", format!("{:1.2} MB", status.resident_set_size as f64 / 1024.0 / 1024.0)),
+                                                            ("Virtual Memory", format!("{:1.2} MB", status.virtual_memory_size as f64 / 1024.0 / 1024.0)),
+                                                            ("File Handles", status.fd_num.separated_string()),
+                                                        ];
+
+                                                        metrics.chunks(5).for_each(|chunk| {
+                                                            ui.horizontal(|ui|{
+                                                                chunk.iter().for_each(|(title, text)| {
+                                                                    ui.horizontal(|ui| {
+                                                                        ui.label(format!("{title}:"));
+                                                                        ui.colored_label(egui::Color32::WHITE, text);
+                                                                        ui.add_space(16.0);
+                                                                    });
+                                                                });
+                                                            });
+                                                        });
                                                     }
                                                 }
 
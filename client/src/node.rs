@@ -24,6 +24,38 @@ impl Machine {
     }
 }
 
+/// Connection state of a node as reported by the monitor.
+pub struct NodeState {
+    pub connected: bool,
+    pub online: bool,
+    pub synced: bool,
+    pub delegate: Option<u64>,
+    // unix time in msec at which the state was entered
+    pub since: u64,
+}
+
+impl NodeState {
+    pub fn name(&self) -> &'static str {
+        if !self.connected {
+            "offline"
+        } else if self.delegate.is_some() {
+            "delegator"
+        } else if !self.online {
+            "unresponsive"
+        } else if self.synced {
+            "online"
+        } else {
+            "syncing"
+        }
+    }
+
+    /// Duration in the current state.
+    pub fn duration(&self) -> Duration {
+        let now = unixtime_as_millis_f64() as u64;
+        Duration::from_millis(now.saturating_sub(self.since))
+    }
+}
+
 pub struct Node {
     pub status: Arc<Status>,
 }
@@ -40,7 +72,7 @@ impl Node {
     pub fn network_id(&self) -> NetworkId {
         match &*self.status {
             Status::Kaspa(status) => status.network_id,
-            Status::Sparkle(_status) => NetworkType::Mainnet.try_into().unwrap(),
+            Status::Sparkle(status) => status.network_id,
         }
     }
 
@@ -73,7 +105,21 @@ impl Node {
                         .pad_to_width_with_alignment(12, Alignment::Right)
                 )
             }
-            Status::Sparkle(_status) => "".to_string(),
+            Status::Sparkle(status) => {
+                format!(
+                    "{} {} clients: {}",
+                    status.network_id.to_string().pad_to_width(12),
+                    if status.is_synced {
+                        "synced "
+                    } else {
+                        "syncing"
+                    },
+                    status
+                        .live_connections()
+                        .separated_string()
+                        .pad_to_width_with_alignment(8, Alignment::Right)
+                )
+            }
         }
     }
 }
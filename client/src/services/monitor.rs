@@ -16,7 +16,12 @@ impl Default for MonitorService {
         Self {
             service_events: Channel::unbounded(),
             task_ctl: Channel::oneshot(),
-            rpc_client: MonitorRpcClient::default(),
+            rpc_client: MonitorRpcClient::try_new_with_token(
+                None,
+                None,
+                std::env::var("KASPA_MONITOR_TOKEN").ok(),
+            )
+            .unwrap(),
         }
     }
 }
@@ -50,6 +55,9 @@ impl Service for MonitorService {
                             Notification::Update { update } => {
                                 runtime.send(Event::Update { update }).await.unwrap();
                             }
+                            Notification::Snapshot { snapshot } => {
+                                runtime.send(Event::Snapshot { snapshot }).await.unwrap();
+                            }
                         }
                     } else {
                         break;
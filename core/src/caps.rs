@@ -9,8 +9,10 @@ pub struct Caps {
     pub system_id: u64,
     // git hash
     pub git_hash: Option<String>,
-    // current memory usage in bytes
+    // total system memory in bytes (0 if not reported by the node)
     pub total_memory: u64,
+    // resident set size of the node process in bytes
+    pub resident_set_size: Option<u64>,
     // number of cores
     pub cpu_physical_cores: u64,
     // number of available file descriptors
@@ -27,7 +29,7 @@ impl Caps {
 
 impl Serializer for Caps {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(String, &self.version, writer)?;
         store!(u64, &self.system_id, writer)?;
         store!(Option<String>, &self.git_hash, writer)?;
@@ -35,13 +37,14 @@ impl Serializer for Caps {
         store!(u64, &self.cpu_physical_cores, writer)?;
         store!(u64, &self.fd_limit, writer)?;
         store!(u64, &self.clients_limit, writer)?;
+        store!(Option<u64>, &self.resident_set_size, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for Caps {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let caps_version = load!(u16, reader)?;
         let version = load!(String, reader)?;
         let system_id = load!(u64, reader)?;
         let git_hash = load!(Option<String>, reader)?;
@@ -49,12 +52,18 @@ impl Deserializer for Caps {
         let cpu_physical_cores = load!(u64, reader)?;
         let fd_limit = load!(u64, reader)?;
         let clients_limit = load!(u64, reader)?;
+        let resident_set_size = if caps_version > 1 {
+            load!(Option<u64>, reader)?
+        } else {
+            None
+        };
 
         Ok(Self {
             version,
             system_id,
             git_hash,
             total_memory,
+            resident_set_size,
             cpu_physical_cores,
             fd_limit,
             clients_limit,
@@ -0,0 +1,78 @@
+use crate::imports::*;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+#[borsh(use_discriminant = true)]
+pub enum DivergenceKind {
+    /// Node pruning point differs from the network majority
+    PruningPoint = 0,
+    /// Node virtual DAA score lags the network maximum
+    DaaLag,
+    /// Node sink has not advanced while other nodes' sinks have
+    SinkStall,
+}
+
+impl std::fmt::Display for DivergenceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            DivergenceKind::PruningPoint => "pruning point",
+            DivergenceKind::DaaLag => "DAA lag",
+            DivergenceKind::SinkStall => "sink stall",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Node state diverging from other nodes on the same network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Divergence {
+    pub uid: u64,
+    pub network_id: NetworkId,
+    pub kind: DivergenceKind,
+    // DAA score lag for `DaaLag`, stall duration in msec for `SinkStall`
+    pub value: u64,
+    // unix time in msec at which the divergence was detected
+    pub since: u64,
+}
+
+impl Serializer for Divergence {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.uid, writer)?;
+        store!(NetworkId, &self.network_id, writer)?;
+        store!(DivergenceKind, &self.kind, writer)?;
+        store!(u64, &self.value, writer)?;
+        store!(u64, &self.since, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for Divergence {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let uid = load!(u64, reader)?;
+        let network_id = load!(NetworkId, reader)?;
+        let kind = load!(DivergenceKind, reader)?;
+        let value = load!(u64, reader)?;
+        let since = load!(u64, reader)?;
+
+        Ok(Self {
+            uid,
+            network_id,
+            kind,
+            value,
+            since,
+        })
+    }
+}
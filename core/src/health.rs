@@ -0,0 +1,108 @@
+use crate::imports::*;
+
+/// RPC call issued by the monitor to a node.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+#[borsh(use_discriminant = true)]
+pub enum RpcMethod {
+    /// Keep-alive of delegator connections
+    Ping = 0,
+    /// Node capabilities, requested after connecting
+    GetCaps,
+    /// Node sync state
+    GetSync,
+    /// Node metrics and DAG state
+    GetStatus,
+}
+
+impl std::fmt::Display for RpcMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            RpcMethod::Ping => "ping",
+            RpcMethod::GetCaps => "get-caps",
+            RpcMethod::GetSync => "get-sync",
+            RpcMethod::GetStatus => "get-status",
+        };
+        f.write_str(s)
+    }
+}
+
+/// RPC health statistics of a node connection.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Health {
+    pub uid: u64,
+    // number of latency samples in the rolling window
+    pub samples: u64,
+    // round-trip latency percentiles over the rolling window in msec
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+    // failed calls by method since the connection was created
+    pub errors: Vec<(RpcMethod, u64)>,
+    // number of times the connection was re-established
+    pub reconnects: u64,
+    // unix time in msec of the last successful and failed calls
+    pub last_success: Option<u64>,
+    pub last_failure: Option<u64>,
+}
+
+impl Health {
+    /// Total number of failed calls.
+    pub fn error_count(&self) -> u64 {
+        self.errors.iter().map(|(_, count)| count).sum()
+    }
+}
+
+impl Serializer for Health {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.uid, writer)?;
+        store!(u64, &self.samples, writer)?;
+        store!(f64, &self.p50, writer)?;
+        store!(f64, &self.p95, writer)?;
+        store!(f64, &self.p99, writer)?;
+        store!(Vec<(RpcMethod, u64)>, &self.errors, writer)?;
+        store!(u64, &self.reconnects, writer)?;
+        store!(Option<u64>, &self.last_success, writer)?;
+        store!(Option<u64>, &self.last_failure, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for Health {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let uid = load!(u64, reader)?;
+        let samples = load!(u64, reader)?;
+        let p50 = load!(f64, reader)?;
+        let p95 = load!(f64, reader)?;
+        let p99 = load!(f64, reader)?;
+        let errors = load!(Vec<(RpcMethod, u64)>, reader)?;
+        let reconnects = load!(u64, reader)?;
+        let last_success = load!(Option<u64>, reader)?;
+        let last_failure = load!(Option<u64>, reader)?;
+
+        Ok(Self {
+            uid,
+            samples,
+            p50,
+            p95,
+            p99,
+            errors,
+            reconnects,
+            last_success,
+            last_failure,
+        })
+    }
+}
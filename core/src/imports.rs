@@ -36,5 +36,5 @@ pub use workflow_serializer::prelude::*;
 
 pub use kaspa_consensus_core::network::NetworkId;
 pub use kaspa_hashes::Hash;
-pub use kaspa_metrics_core::{MetricsData, MetricsSnapshot};
+pub use kaspa_metrics_core::{Metric, MetricsData, MetricsSnapshot};
 pub use kaspa_rpc_core::GetBlockDagInfoResponse;
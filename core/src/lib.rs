@@ -1,12 +1,15 @@
 // pub mod constants;
 pub mod debug;
+pub mod divergence;
 pub mod error;
+pub mod health;
 // pub mod hash;
 // pub mod id;
 pub mod imports;
 // pub mod inscription;
 pub mod caps;
 pub mod model;
+pub mod node;
 pub mod prelude;
 pub mod result;
 pub mod status;
@@ -0,0 +1,52 @@
+use crate::imports::*;
+
+/// Node configuration as seen by monitor clients.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeInfo {
+    pub uid: u64,
+    // service type (kaspa, sparkle)
+    pub service: String,
+    // protocol+encoding (wrpc-borsh, wrpc-json, grpc)
+    pub transport: String,
+    pub tls: bool,
+    pub network_id: NetworkId,
+    pub fqdn: String,
+    pub address: String,
+}
+
+impl Serializer for NodeInfo {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.uid, writer)?;
+        store!(String, &self.service, writer)?;
+        store!(String, &self.transport, writer)?;
+        store!(bool, &self.tls, writer)?;
+        store!(NetworkId, &self.network_id, writer)?;
+        store!(String, &self.fqdn, writer)?;
+        store!(String, &self.address, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NodeInfo {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let uid = load!(u64, reader)?;
+        let service = load!(String, reader)?;
+        let transport = load!(String, reader)?;
+        let tls = load!(bool, reader)?;
+        let network_id = load!(NetworkId, reader)?;
+        let fqdn = load!(String, reader)?;
+        let address = load!(String, reader)?;
+
+        Ok(Self {
+            uid,
+            service,
+            transport,
+            tls,
+            network_id,
+            fqdn,
+            address,
+        })
+    }
+}
@@ -1,4 +1,7 @@
 pub use crate::caps::Caps;
+pub use crate::divergence::{Divergence, DivergenceKind};
+pub use crate::health::{Health, RpcMethod};
+pub use crate::node::NodeInfo;
 pub use crate::status::{KaspaNodeStatus, SparkleNodeStatus, Status};
-pub use kaspa_metrics_core::{MetricsData, MetricsSnapshot};
+pub use kaspa_metrics_core::{Metric, MetricsData, MetricsSnapshot};
 pub use kaspa_rpc_core::GetBlockDagInfoResponse;
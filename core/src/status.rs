@@ -21,6 +21,58 @@ impl Status {
         }
     }
 
+    pub fn is_synced(&self) -> bool {
+        match self {
+            Status::Kaspa(status) => status.is_synced,
+            Status::Sparkle(status) => status.is_synced,
+        }
+    }
+
+    pub fn network_id(&self) -> NetworkId {
+        match self {
+            Status::Kaspa(status) => status.network_id,
+            Status::Sparkle(status) => status.network_id,
+        }
+    }
+
+    /// Virtual DAA score reported by Kaspa nodes.
+    pub fn virtual_daa_score(&self) -> Option<u64> {
+        match self {
+            Status::Kaspa(status) => Some(status.virtual_daa_score),
+            Status::Sparkle(_) => None,
+        }
+    }
+
+    /// Number of live wRPC client connections (Borsh and JSON) of the node process.
+    pub fn clients(&self) -> u64 {
+        match self {
+            Status::Kaspa(status) => {
+                let snapshot = &status.metrics_snapshot;
+                (snapshot.get(&Metric::NodeBorshLiveConnections)
+                    + snapshot.get(&Metric::NodeJsonLiveConnections)) as u64
+            }
+            Status::Sparkle(status) => status.live_connections(),
+        }
+    }
+
+    /// Number of active p2p peers of the node.
+    pub fn peers(&self) -> u64 {
+        match self {
+            Status::Kaspa(status) => status.metrics_snapshot.get(&Metric::NodeActivePeers) as u64,
+            Status::Sparkle(status) => status.active_peers,
+        }
+    }
+
+    /// Value of the given [`Metric`] in this status sample. Sparkle
+    /// nodes report only a subset of metrics; `None` is returned
+    /// for metrics not available from the node.
+    pub fn metric(&self, metric: &Metric) -> Option<f64> {
+        match self {
+            Status::Kaspa(status) => Some(status.metrics_snapshot.get(metric)),
+            Status::Sparkle(status) => status.metric(metric),
+        }
+    }
+
     pub fn name(&self) -> String {
         match self {
             Status::Kaspa(_) => "Kaspa",
@@ -39,7 +91,7 @@ impl Serializer for Status {
                 serialize!(KaspaNodeStatus, &status, writer)?;
             }
             Status::Sparkle(status) => {
-                store!(u8, &1, writer)?;
+                store!(u8, &2, writer)?;
                 serialize!(SparkleNodeStatus, &status, writer)?;
             }
         }
@@ -161,13 +213,56 @@ impl Deserializer for KaspaNodeStatus {
 pub struct SparkleNodeStatus {
     pub sid: u64,
     pub uid: u64,
+    pub is_synced: bool,
+    pub network_id: NetworkId,
+    // live client connections
+    pub borsh_live_connections: u64,
+    pub json_live_connections: u64,
+    // connected kaspa nodes
+    pub active_peers: u64,
+    // process metrics
+    pub resident_set_size: u64,
+    pub virtual_memory_size: u64,
+    pub core_num: u64,
+    pub cpu_usage: f64,
+    pub fd_num: u64,
+}
+
+impl SparkleNodeStatus {
+    pub fn live_connections(&self) -> u64 {
+        self.borsh_live_connections + self.json_live_connections
+    }
+
+    pub fn metric(&self, metric: &Metric) -> Option<f64> {
+        let value = match metric {
+            Metric::NodeCpuUsage => self.cpu_usage,
+            Metric::NodeResidentSetSizeBytes => self.resident_set_size as f64,
+            Metric::NodeVirtualMemorySizeBytes => self.virtual_memory_size as f64,
+            Metric::NodeFileHandlesCount => self.fd_num as f64,
+            Metric::NodeActivePeers => self.active_peers as f64,
+            Metric::NodeBorshLiveConnections => self.borsh_live_connections as f64,
+            Metric::NodeJsonLiveConnections => self.json_live_connections as f64,
+            _ => return None,
+        };
+        Some(value)
+    }
 }
 
 impl Serializer for SparkleNodeStatus {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(u64, &self.sid, writer)?;
         store!(u64, &self.uid, writer)?;
+        store!(bool, &self.is_synced, writer)?;
+        store!(NetworkId, &self.network_id, writer)?;
+        store!(u64, &self.borsh_live_connections, writer)?;
+        store!(u64, &self.json_live_connections, writer)?;
+        store!(u64, &self.active_peers, writer)?;
+        store!(u64, &self.resident_set_size, writer)?;
+        store!(u64, &self.virtual_memory_size, writer)?;
+        store!(u64, &self.core_num, writer)?;
+        store!(f64, &self.cpu_usage, writer)?;
+        store!(u64, &self.fd_num, writer)?;
         Ok(())
     }
 }
@@ -177,6 +272,30 @@ impl Deserializer for SparkleNodeStatus {
         let _version = load!(u16, reader)?;
         let sid = load!(u64, reader)?;
         let uid = load!(u64, reader)?;
-        Ok(Self { sid, uid })
+        let is_synced = load!(bool, reader)?;
+        let network_id = load!(NetworkId, reader)?;
+        let borsh_live_connections = load!(u64, reader)?;
+        let json_live_connections = load!(u64, reader)?;
+        let active_peers = load!(u64, reader)?;
+        let resident_set_size = load!(u64, reader)?;
+        let virtual_memory_size = load!(u64, reader)?;
+        let core_num = load!(u64, reader)?;
+        let cpu_usage = load!(f64, reader)?;
+        let fd_num = load!(u64, reader)?;
+
+        Ok(Self {
+            sid,
+            uid,
+            is_synced,
+            network_id,
+            borsh_live_connections,
+            json_live_connections,
+            active_peers,
+            resident_set_size,
+            virtual_memory_size,
+            core_num,
+            cpu_usage,
+            fd_num,
+        })
     }
 }
@@ -0,0 +1,166 @@
+use crate::imports::*;
+
+// samples as recorded
+const RAW: &str = "raw";
+// samples older than the raw retention period
+const DOWNSAMPLED: &str = "downsampled";
+
+/// Database key: big-endian `uid` followed by big-endian `timestamp`,
+/// ensuring that samples are ordered by node and then by time.
+fn key(uid: u64, timestamp: u64) -> [u8; 16] {
+    let mut key = [0u8; 16];
+    key[0..8].copy_from_slice(&uid.to_be_bytes());
+    key[8..16].copy_from_slice(&timestamp.to_be_bytes());
+    key
+}
+
+fn parse_key(key: &[u8]) -> Result<(u64, u64)> {
+    if key.len() != 16 {
+        return Err(Error::KeyLength(key.len()));
+    }
+    let uid = u64::from_be_bytes(key[0..8].try_into().unwrap());
+    let timestamp = u64::from_be_bytes(key[8..16].try_into().unwrap());
+    Ok((uid, timestamp))
+}
+
+/// Compaction results
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Compaction {
+    pub downsampled: usize,
+    pub removed: usize,
+}
+
+/// Time-series storage of node [`Status`] samples keyed by node uid and timestamp.
+pub struct Database {
+    db: DB,
+}
+
+impl Database {
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+        let db = DB::open_cf(&options, path, [RAW, DOWNSAMPLED])?;
+        Ok(Self { db })
+    }
+
+    fn cf(&self, name: &'static str) -> Result<&ColumnFamily> {
+        self.db.cf_handle(name).ok_or(Error::ColumnFamily(name))
+    }
+
+    /// Append a status sample recorded at `timestamp` (unix time in msec).
+    pub fn store(&self, timestamp: u64, status: &Status) -> Result<()> {
+        let mut data = Vec::new();
+        status.serialize(&mut data)?;
+        self.db
+            .put_cf(self.cf(RAW)?, key(status.uid(), timestamp), data)?;
+        Ok(())
+    }
+
+    /// Obtain samples for the node `uid` in the `from..=to` time range
+    /// (unix time in msec), ordered by timestamp.
+    pub fn range(&self, uid: u64, from: u64, to: u64) -> Result<Vec<Sample>> {
+        // downsampled samples always precede raw samples
+        let mut samples = self.range_cf(DOWNSAMPLED, uid, from, to)?;
+        samples.extend(self.range_cf(RAW, uid, from, to)?);
+        Ok(samples)
+    }
+
+    fn range_cf(&self, name: &'static str, uid: u64, from: u64, to: u64) -> Result<Vec<Sample>> {
+        let mut samples = Vec::new();
+        let mut iter = self.db.raw_iterator_cf(self.cf(name)?);
+        iter.seek(key(uid, from));
+        while iter.valid() {
+            let (Some(k), Some(v)) = (iter.key(), iter.value()) else {
+                break;
+            };
+            let (sample_uid, timestamp) = parse_key(k)?;
+            if sample_uid != uid || timestamp > to {
+                break;
+            }
+            match Status::deserialize(&mut &v[..]) {
+                Ok(status) => samples.push(Sample::new(timestamp, Arc::new(status))),
+                Err(err) => log_warn!("Database: unable to deserialize sample: {err}"),
+            }
+            iter.next();
+        }
+        iter.status()?;
+        Ok(samples)
+    }
+
+    /// Apply the [`Retention`] policy: downsample raw samples older than
+    /// the raw retention period and remove samples older than the history
+    /// retention period. `now` is the current unix time in msec.
+    pub fn compact(&self, retention: &Retention, now: u64) -> Result<Compaction> {
+        let raw = self.cf(RAW)?;
+        let downsampled = self.cf(DOWNSAMPLED)?;
+
+        let raw_cutoff = retention.raw_cutoff(now);
+        let history_cutoff = retention.history_cutoff(now);
+        let period = retention.downsample_millis();
+
+        let mut compaction = Compaction::default();
+        let mut batch = WriteBatch::default();
+
+        // raw samples: keep the first sample of each downsampling period
+        let mut iter = self.db.raw_iterator_cf(raw);
+        iter.seek_to_first();
+        let mut last_bucket = None;
+        while iter.valid() {
+            let (Some(k), Some(v)) = (iter.key(), iter.value()) else {
+                break;
+            };
+            let (uid, timestamp) = parse_key(k)?;
+            if timestamp >= raw_cutoff {
+                // skip to the next node
+                match uid.checked_add(1) {
+                    Some(next) => {
+                        iter.seek(key(next, 0));
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+
+            if timestamp >= history_cutoff {
+                let bucket = (uid, timestamp / period);
+                if last_bucket != Some(bucket) {
+                    last_bucket = Some(bucket);
+                    batch.put_cf(downsampled, k, v);
+                    compaction.downsampled += 1;
+                }
+            }
+            batch.delete_cf(raw, k);
+            compaction.removed += 1;
+            iter.next();
+        }
+        iter.status()?;
+
+        // downsampled samples: remove samples beyond the history period
+        let mut iter = self.db.raw_iterator_cf(downsampled);
+        iter.seek_to_first();
+        while iter.valid() {
+            let Some(k) = iter.key() else {
+                break;
+            };
+            let (uid, timestamp) = parse_key(k)?;
+            if timestamp >= history_cutoff {
+                match uid.checked_add(1) {
+                    Some(next) => {
+                        iter.seek(key(next, 0));
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+            batch.delete_cf(downsampled, k);
+            compaction.removed += 1;
+            iter.next();
+        }
+        iter.status()?;
+
+        self.db.write(batch)?;
+
+        Ok(compaction)
+    }
+}
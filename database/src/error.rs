@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Error: {0}")]
+    Custom(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    RocksDb(#[from] rocksdb::Error),
+
+    #[error("Missing column family: {0}")]
+    ColumnFamily(&'static str),
+
+    #[error("Invalid key length: {0}")]
+    KeyLength(usize),
+}
+
+impl Error {
+    pub fn custom<T: Into<String>>(msg: T) -> Self {
+        Error::Custom(msg.into())
+    }
+}
@@ -0,0 +1,14 @@
+pub use std::path::{Path, PathBuf};
+pub use std::sync::Arc;
+pub use std::time::Duration;
+
+pub use rocksdb::{ColumnFamily, Options, WriteBatch, DB};
+pub use workflow_log::prelude::*;
+pub use workflow_serializer::prelude::*;
+
+pub use kaspa_monitor_core::status::Status;
+
+pub use crate::error::Error;
+pub use crate::result::Result;
+pub use crate::retention::Retention;
+pub use crate::sample::Sample;
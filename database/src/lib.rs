@@ -0,0 +1,19 @@
+cfg_if::cfg_if! {
+    if #[cfg(not(target_arch = "wasm32"))] {
+
+        #[allow(clippy::module_inception)]
+        pub mod database;
+
+        pub mod error;
+        pub mod imports;
+        pub mod result;
+        pub mod retention;
+        pub mod sample;
+
+        pub mod prelude {
+            pub use crate::database::Database;
+            pub use crate::retention::Retention;
+            pub use crate::sample::Sample;
+        }
+    }
+}
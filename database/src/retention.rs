@@ -0,0 +1,33 @@
+use crate::imports::*;
+
+/// Status history retention policy.
+///
+/// Samples younger than `raw` are kept as recorded. Older samples are
+/// downsampled to one sample per `downsample` period and samples older
+/// than `history` are discarded.
+#[derive(Debug, Clone)]
+pub struct Retention {
+    pub raw: Duration,
+    pub downsample: Duration,
+    pub history: Duration,
+}
+
+impl Retention {
+    /// Timestamp (msec) before which raw samples are downsampled.
+    /// Aligned to the downsampling period so that a downsampling
+    /// bucket is always processed in its entirety.
+    pub fn raw_cutoff(&self, now: u64) -> u64 {
+        let downsample = self.downsample_millis();
+        let cutoff = now.saturating_sub(self.raw.as_millis() as u64);
+        cutoff / downsample * downsample
+    }
+
+    /// Timestamp (msec) before which all samples are discarded.
+    pub fn history_cutoff(&self, now: u64) -> u64 {
+        now.saturating_sub(self.history.as_millis() as u64)
+    }
+
+    pub fn downsample_millis(&self) -> u64 {
+        (self.downsample.as_millis() as u64).max(1)
+    }
+}
@@ -0,0 +1,18 @@
+use crate::imports::*;
+
+/// Node [`Status`] recorded at `timestamp` (unix time in msec).
+#[derive(Debug, Clone)]
+pub struct Sample {
+    pub timestamp: u64,
+    pub status: Arc<Status>,
+}
+
+impl Sample {
+    pub fn new(timestamp: u64, status: Arc<Status>) -> Self {
+        Self { timestamp, status }
+    }
+
+    pub fn uid(&self) -> u64 {
+        self.status.uid()
+    }
+}
@@ -0,0 +1,34 @@
+pub use ahash::AHashMap;
+pub use async_trait::async_trait;
+pub use serde::{Deserialize, Serialize};
+pub use std::collections::VecDeque;
+pub use std::net::{IpAddr, SocketAddr};
+pub use std::sync::{Arc, Mutex};
+pub use std::time::Instant;
+pub use xxhash_rust::xxh3::xxh3_64;
+
+pub use workflow_core::channel::Channel;
+pub use workflow_core::enums::Describe;
+pub use workflow_log::prelude::*;
+
+pub use axum::{
+    extract::{ConnectInfo, Form, Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+
+pub use kaspa_monitor_core::caps::Caps;
+pub use kaspa_monitor_core::prelude::Metric;
+pub use kaspa_monitor_core::runtime::{Runtime, Service, ServiceError, ServiceResult};
+pub use kaspa_monitor_nexus::config::{load_key64, HttpStatus};
+pub use kaspa_monitor_nexus::connection::Connection;
+pub use kaspa_monitor_nexus::outbox::NotificationStats;
+pub use kaspa_monitor_nexus::prelude::Nexus;
+pub use kaspa_monitor_nexus::resolver::Target;
+pub use kaspa_monitor_rpc_core::prelude::{Availability, UptimeReport};
+
+pub use crate::error::Error;
+pub use crate::result::Result;
+pub use crate::session::{LoginThrottle, Sessions};
@@ -0,0 +1,16 @@
+cfg_if::cfg_if! {
+    if #[cfg(not(target_arch = "wasm32"))] {
+
+        pub mod error;
+        pub mod imports;
+        pub mod metrics;
+        pub mod result;
+        pub mod service;
+        pub mod session;
+        pub mod status;
+        pub mod uptime;
+
+        pub use service::{HttpOptions, HttpService};
+
+    }
+}
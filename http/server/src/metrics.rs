@@ -0,0 +1,171 @@
+use crate::imports::*;
+use convert_case::{Case, Casing};
+use std::fmt::Write;
+
+const PREFIX: &str = "kaspa_monitor";
+
+struct Family {
+    name: String,
+    help: String,
+    // metric type: `gauge` or `counter`
+    kind: &'static str,
+    samples: Vec<(String, f64)>,
+}
+
+impl Family {
+    fn new(name: &str, help: &str) -> Self {
+        Self {
+            name: format!("{PREFIX}_{name}"),
+            help: help.to_string(),
+            kind: "gauge",
+            samples: vec![],
+        }
+    }
+
+    /// Monotonically increasing counter, named with the `_total` suffix.
+    fn counter(name: &str, help: &str) -> Self {
+        Self {
+            name: format!("{PREFIX}_{name}_total"),
+            help: help.to_string(),
+            kind: "counter",
+            samples: vec![],
+        }
+    }
+
+    fn push(&mut self, labels: &str, value: f64) {
+        self.samples.push((labels.to_string(), value));
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn labels(connection: &Connection) -> String {
+    let node = connection.node();
+    format!(
+        "uid=\"{}\",sid=\"{:016x}\",fqdn=\"{}\",network=\"{}\",transport=\"{}\",service=\"{}\"",
+        node.uid_as_str(),
+        connection.sid(),
+        escape(&node.fqdn),
+        connection.network_id(),
+        node.transport_kind(),
+        node.service(),
+    )
+}
+
+fn as_gauge(value: bool) -> f64 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Render connection state, caps and node metrics of all connections
+/// and notification delivery counters in the Prometheus text exposition
+/// format.
+pub fn render(connections: &[Arc<Connection>], notifications: &NotificationStats) -> String {
+    let mut connected = Family::new("connected", "Node RPC connection is established");
+    let mut online = Family::new("online", "Node is responding to RPC requests");
+    let mut synced = Family::new("synced", "Node is synced");
+    let mut delegate = Family::new(
+        "delegate",
+        "Connection is the primary (delegate) connection to the node",
+    );
+    let mut clients = Family::new("clients", "Number of connected RPC clients");
+    let mut peers = Family::new("peers", "Number of connected p2p peers");
+    let mut fd_limit = Family::new("caps_fd_limit", "File descriptor limit");
+    let mut clients_limit = Family::new("caps_clients_limit", "RPC client limit");
+    let mut cpu_physical_cores = Family::new("caps_cpu_physical_cores", "Physical CPU cores");
+    let mut total_memory = Family::new("caps_total_memory_bytes", "Total memory");
+    let mut subscribers = Family::new("subscribers", "Number of connected monitor clients");
+    let mut notifications_dropped = Family::counter(
+        "notifications_dropped",
+        "Notifications dropped, coalesced or failed to be delivered to monitor clients",
+    );
+    let mut subscribers_disconnected = Family::counter(
+        "subscribers_disconnected",
+        "Monitor clients disconnected for lagging behind",
+    );
+
+    subscribers.push("", notifications.clients as f64);
+    notifications_dropped.push("", notifications.dropped as f64);
+    subscribers_disconnected.push("", notifications.disconnected as f64);
+
+    let metrics = Metric::list();
+    let mut families = metrics
+        .iter()
+        .map(|metric| {
+            let name = metric.as_str().to_case(Case::Snake);
+            Family::new(name.as_str(), metric.title().0)
+        })
+        .collect::<Vec<_>>();
+
+    for connection in connections {
+        let labels = labels(connection);
+
+        connected.push(&labels, as_gauge(connection.connected()));
+        online.push(&labels, as_gauge(connection.online()));
+        synced.push(&labels, as_gauge(connection.is_synced()));
+        delegate.push(&labels, as_gauge(connection.is_delegate()));
+        clients.push(&labels, connection.clients() as f64);
+        peers.push(&labels, connection.peers() as f64);
+
+        if let Some(caps) = connection.caps() {
+            fd_limit.push(&labels, caps.fd_limit as f64);
+            clients_limit.push(&labels, caps.clients_limit as f64);
+            cpu_physical_cores.push(&labels, caps.cpu_physical_cores as f64);
+            // not reported by Sparkle nodes
+            if caps.total_memory > 0 {
+                total_memory.push(&labels, caps.total_memory as f64);
+            }
+        }
+
+        if let Some(status) = connection.last_status() {
+            for (metric, family) in metrics.iter().zip(families.iter_mut()) {
+                if let Some(value) = status.metric(metric) {
+                    family.push(&labels, value);
+                }
+            }
+        }
+    }
+
+    let mut text = String::new();
+    for family in [
+        connected,
+        online,
+        synced,
+        delegate,
+        clients,
+        peers,
+        fd_limit,
+        clients_limit,
+        cpu_physical_cores,
+        total_memory,
+        subscribers,
+        notifications_dropped,
+        subscribers_disconnected,
+    ]
+    .into_iter()
+    .chain(families)
+    .filter(|family| !family.samples.is_empty())
+    {
+        let Family {
+            name,
+            help,
+            kind,
+            samples,
+        } = family;
+        writeln!(text, "# HELP {name} {}", escape(&help)).ok();
+        writeln!(text, "# TYPE {name} {kind}").ok();
+        for (labels, value) in samples {
+            writeln!(text, "{name}{{{labels}}} {value}").ok();
+        }
+    }
+
+    text
+}
@@ -0,0 +1,370 @@
+use crate::imports::*;
+use crate::session::Sessions;
+use crate::status::{ConnectionStatus, MachineStatus, Resolution};
+use kaspa_monitor_nexus::error::Error as NexusError;
+use subtle::ConstantTimeEq;
+
+/// Options for configuring the HTTP server
+pub struct HttpOptions {
+    pub listen_address: String,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        HttpOptions {
+            listen_address: "127.0.0.1:8080".to_owned(),
+        }
+    }
+}
+
+impl HttpOptions {
+    pub fn listen(mut self, address: &str) -> Self {
+        address.clone_into(&mut self.listen_address);
+        self
+    }
+}
+
+struct Inner {
+    nexus: Nexus,
+    sessions: Sessions,
+    // `None` if the monitor key is not installed
+    key64: Option<u64>,
+    login_throttle: LoginThrottle,
+}
+
+#[derive(Deserialize)]
+struct Login {
+    password: String,
+}
+
+/// HTTP server exposing the monitor status as a JSON API.
+/// Access requires a session obtained by posting the monitor
+/// key password to `/login` or HTTP basic authentication
+/// with the same password (e.g. for Prometheus scrapers).
+/// The resolver endpoint (`/v2/...`) is public.
+///
+/// The key is loaded when the service is created; without a
+/// key, only the resolver endpoint is available. Clients are
+/// locked out after repeated failed `/login` or basic
+/// authentication attempts.
+pub struct HttpService {
+    inner: Arc<Inner>,
+    options: HttpOptions,
+    terminate: Channel<()>,
+    shutdown: Channel<()>,
+}
+
+impl HttpService {
+    pub fn try_new(nexus: &Nexus, options: HttpOptions) -> Result<Self> {
+        let key64 = match load_key64() {
+            Ok(key64) => Some(key64),
+            Err(NexusError::KeyNotFound) => {
+                log_warn!("HTTP: key not found, status endpoints are unavailable");
+                None
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(HttpService {
+            inner: Arc::new(Inner {
+                nexus: nexus.clone(),
+                sessions: Sessions::default(),
+                key64,
+                login_throttle: LoginThrottle::default(),
+            }),
+            options,
+            terminate: Channel::oneshot(),
+            shutdown: Channel::oneshot(),
+        })
+    }
+
+    fn router(&self) -> Router {
+        Router::new()
+            .route("/login", post(login))
+            .route("/logout", post(logout))
+            .route("/status", get(status))
+            .route("/node/:uid", get(node))
+            .route("/machines", get(machines))
+            .route("/metrics", get(metrics))
+            .route("/uptime", get(uptime))
+            .route("/uptime.csv", get(uptime_csv))
+            .route("/outages.csv", get(outages_csv))
+            .route("/v2/:service/:network/:tls/:encoding", get(resolve))
+            .with_state(self.inner.clone())
+    }
+}
+
+#[async_trait]
+impl Service for HttpService {
+    async fn spawn(self: Arc<Self>, _runtime: Runtime) -> ServiceResult<()> {
+        let listen_address = self.options.listen_address.clone();
+        log_info!("HTTP server listening on: {}", listen_address);
+        let listener = tokio::net::TcpListener::bind(listen_address.as_str())
+            .await
+            .map_err(ServiceError::custom)?;
+
+        let router = self.router();
+        tokio::spawn(async move {
+            let terminate = self.terminate.receiver.clone();
+            let serve_result = axum::serve(
+                listener,
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .with_graceful_shutdown(async move {
+                terminate.recv().await.ok();
+            })
+            .await;
+            match serve_result {
+                Ok(_) => log_info!("HTTP server stopped on: {}", listen_address),
+                Err(err) => log_error!("HTTP server {listen_address} stopped with error: {err}"),
+            }
+            self.shutdown.send(()).await.unwrap();
+        });
+
+        Ok(())
+    }
+
+    fn terminate(self: Arc<Self>) {
+        self.terminate
+            .try_send(())
+            .unwrap_or_else(|err| log_warn!("HTTP unable to signal shutdown: `{err}`"));
+    }
+
+    async fn join(self: Arc<Self>) -> ServiceResult<()> {
+        self.shutdown.recv().await?;
+        Ok(())
+    }
+}
+
+fn is_valid_password(inner: &Inner, password: &str) -> bool {
+    inner.key64.is_some_and(|key64| {
+        let hash = xxh3_64(password.as_bytes()).to_be_bytes();
+        hash.ct_eq(&key64.to_be_bytes()).into()
+    })
+}
+
+// HTTP basic authentication password (the user name is ignored),
+// allowing scrapers and scripts to authenticate without a session
+fn basic_auth(headers: &HeaderMap) -> Option<String> {
+    use base64::prelude::*;
+
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Basic "))
+        .and_then(|credentials| BASE64_STANDARD.decode(credentials.trim()).ok())
+        .and_then(|credentials| String::from_utf8(credentials).ok())
+        .and_then(|credentials| {
+            credentials
+                .split_once(':')
+                .map(|(_, password)| password.to_string())
+        })
+}
+
+// verify the password submitted by the client at `ip`, locking
+// the client out after repeated failed attempts
+fn check_password(inner: &Inner, ip: IpAddr, password: &str) -> std::result::Result<(), Response> {
+    if inner.login_throttle.is_locked(&ip) {
+        return Err((StatusCode::TOO_MANY_REQUESTS, "Too many failed attempts").into_response());
+    }
+
+    if !is_valid_password(inner, password) {
+        log_warn!("HTTP: failed authentication attempt from {ip}");
+        inner.login_throttle.fail(ip);
+        return Err((StatusCode::UNAUTHORIZED, "Unauthorized").into_response());
+    }
+    inner.login_throttle.reset(&ip);
+
+    Ok(())
+}
+
+fn authorize(
+    inner: &Inner,
+    addr: &SocketAddr,
+    headers: &HeaderMap,
+) -> std::result::Result<(), Response> {
+    match Sessions::from_headers(headers) {
+        Some(id) if inner.sessions.is_valid(&id) => Ok(()),
+        _ => match basic_auth(headers) {
+            Some(password) => check_password(inner, addr.ip(), &password),
+            None => Err((StatusCode::UNAUTHORIZED, "Unauthorized").into_response()),
+        },
+    }
+}
+
+fn set_cookie(cookie: String) -> Response {
+    match HeaderValue::from_str(&cookie) {
+        Ok(cookie) => (StatusCode::OK, [(header::SET_COOKIE, cookie)]).into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+async fn login(
+    State(inner): State<Arc<Inner>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Form(login): Form<Login>,
+) -> Response {
+    if inner.key64.is_none() {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Service unavailable").into_response();
+    }
+
+    if let Err(response) = check_password(&inner, addr.ip(), &login.password) {
+        return response;
+    }
+
+    let id = inner.sessions.create();
+    set_cookie(Sessions::cookie(&id))
+}
+
+async fn logout(State(inner): State<Arc<Inner>>, headers: HeaderMap) -> Response {
+    if let Some(id) = Sessions::from_headers(&headers) {
+        inner.sessions.remove(&id);
+    }
+    set_cookie(Sessions::clear_cookie())
+}
+
+async fn status(
+    State(inner): State<Arc<Inner>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(response) = authorize(&inner, &addr, &headers) {
+        return response;
+    }
+
+    let connections = inner
+        .nexus
+        .connections()
+        .iter()
+        .map(ConnectionStatus::from)
+        .collect::<Vec<_>>();
+    Json(connections).into_response()
+}
+
+async fn node(
+    State(inner): State<Arc<Inner>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(uid): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(response) = authorize(&inner, &addr, &headers) {
+        return response;
+    }
+
+    let Ok(uid) = u64::from_str_radix(&uid, 16) else {
+        return (StatusCode::BAD_REQUEST, "Invalid node uid").into_response();
+    };
+
+    match inner
+        .nexus
+        .connections()
+        .iter()
+        .find(|connection| connection.uid() == uid)
+    {
+        Some(connection) => Json(ConnectionStatus::from(connection)).into_response(),
+        None => (StatusCode::NOT_FOUND, "Node not found").into_response(),
+    }
+}
+
+async fn machines(
+    State(inner): State<Arc<Inner>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(response) = authorize(&inner, &addr, &headers) {
+        return response;
+    }
+
+    let connections = inner.nexus.connections();
+    Json(MachineStatus::collect(&connections)).into_response()
+}
+
+async fn metrics(
+    State(inner): State<Arc<Inner>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(response) = authorize(&inner, &addr, &headers) {
+        return response;
+    }
+
+    let connections = inner.nexus.connections();
+    (
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/plain; version=0.0.4"),
+        )],
+        crate::metrics::render(&connections, &inner.nexus.notification_stats()),
+    )
+        .into_response()
+}
+
+fn uptime_report(
+    inner: &Inner,
+    addr: &SocketAddr,
+    headers: &HeaderMap,
+) -> std::result::Result<UptimeReport, Response> {
+    authorize(inner, addr, headers)?;
+
+    match inner.nexus.uptime() {
+        Some(uptime) => Ok(uptime.report()),
+        None => Err((StatusCode::NOT_FOUND, "Uptime tracking is not enabled").into_response()),
+    }
+}
+
+fn csv(body: String) -> Response {
+    (
+        [(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"))],
+        body,
+    )
+        .into_response()
+}
+
+async fn uptime(
+    State(inner): State<Arc<Inner>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Response {
+    match uptime_report(&inner, &addr, &headers) {
+        Ok(report) => Json(report).into_response(),
+        Err(response) => response,
+    }
+}
+
+async fn uptime_csv(
+    State(inner): State<Arc<Inner>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Response {
+    match uptime_report(&inner, &addr, &headers) {
+        Ok(report) => csv(crate::uptime::availability_csv(&report)),
+        Err(response) => response,
+    }
+}
+
+async fn outages_csv(
+    State(inner): State<Arc<Inner>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Response {
+    match uptime_report(&inner, &addr, &headers) {
+        Ok(report) => csv(crate::uptime::outages_csv(&report)),
+        Err(response) => response,
+    }
+}
+
+/// Resolver endpoint responding with the least loaded available node.
+async fn resolve(
+    State(inner): State<Arc<Inner>>,
+    Path((service, network, tls, encoding)): Path<(String, String, String, String)>,
+) -> Response {
+    let target = match Target::try_parse(&service, &network, &tls, &encoding) {
+        Ok(target) => target,
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()).into_response(),
+    };
+
+    match inner.nexus.resolve(&target) {
+        Some(connection) => Json(Resolution::from(&connection)).into_response(),
+        None => (StatusCode::NOT_FOUND, "No nodes available").into_response(),
+    }
+}
@@ -0,0 +1,113 @@
+use crate::imports::*;
+
+pub const SESSION_COOKIE: &str = "kaspa-monitor-session";
+
+/// HTTP status sessions. The number of concurrent sessions is capped by
+/// [`HttpStatus::sessions()`]; sessions expire after [`HttpStatus::ttl()`].
+#[derive(Default)]
+pub struct Sessions {
+    sessions: Mutex<AHashMap<String, Instant>>,
+}
+
+impl Sessions {
+    /// Create a new session, evicting the oldest session
+    /// if the session limit has been reached.
+    pub fn create(&self) -> String {
+        let mut sessions = self.sessions.lock().unwrap();
+        Self::purge(&mut sessions);
+
+        while sessions.len() >= HttpStatus::sessions() {
+            let Some(oldest) = sessions
+                .iter()
+                .min_by_key(|(_, created)| **created)
+                .map(|(id, _)| id.clone())
+            else {
+                break;
+            };
+            sessions.remove(&oldest);
+        }
+
+        let id = format!("{:032x}", rand::random::<u128>());
+        sessions.insert(id.clone(), Instant::now());
+        id
+    }
+
+    pub fn is_valid(&self, id: &str) -> bool {
+        let mut sessions = self.sessions.lock().unwrap();
+        Self::purge(&mut sessions);
+        sessions.contains_key(id)
+    }
+
+    pub fn remove(&self, id: &str) {
+        self.sessions.lock().unwrap().remove(id);
+    }
+
+    fn purge(sessions: &mut AHashMap<String, Instant>) {
+        let ttl = HttpStatus::ttl();
+        sessions.retain(|_, created| created.elapsed() < ttl);
+    }
+
+    /// Extract the session id from the request cookies.
+    pub fn from_headers(headers: &HeaderMap) -> Option<String> {
+        headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|cookies| cookies.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find(|(name, _)| *name == SESSION_COOKIE)
+            .map(|(_, value)| value.to_string())
+    }
+
+    /// `Set-Cookie` header value for the given session id.
+    pub fn cookie(id: &str) -> String {
+        let max_age = HttpStatus::ttl().as_secs();
+        format!("{SESSION_COOKIE}={id}; Path=/; Max-Age={max_age}; HttpOnly; SameSite=Strict")
+    }
+
+    /// `Set-Cookie` header value removing the session cookie.
+    pub fn clear_cookie() -> String {
+        format!("{SESSION_COOKIE}=; Path=/; Max-Age=0; HttpOnly; SameSite=Strict")
+    }
+}
+
+/// Failed `/login` and basic authentication attempts by client address. Clients are locked out for
+/// [`HttpStatus::login_lockout()`] once [`HttpStatus::login_attempts()`]
+/// attempts failed within that period.
+#[derive(Default)]
+pub struct LoginThrottle {
+    failures: Mutex<AHashMap<IpAddr, VecDeque<Instant>>>,
+}
+
+impl LoginThrottle {
+    pub fn is_locked(&self, addr: &IpAddr) -> bool {
+        let mut failures = self.failures.lock().unwrap();
+        Self::purge(&mut failures);
+        failures
+            .get(addr)
+            .is_some_and(|failures| failures.len() >= HttpStatus::login_attempts())
+    }
+
+    pub fn fail(&self, addr: IpAddr) {
+        let mut failures = self.failures.lock().unwrap();
+        Self::purge(&mut failures);
+        failures.entry(addr).or_default().push_back(Instant::now());
+    }
+
+    pub fn reset(&self, addr: &IpAddr) {
+        self.failures.lock().unwrap().remove(addr);
+    }
+
+    fn purge(failures: &mut AHashMap<IpAddr, VecDeque<Instant>>) {
+        let lockout = HttpStatus::login_lockout();
+        failures.retain(|_, failures| {
+            while failures
+                .front()
+                .is_some_and(|failed| failed.elapsed() >= lockout)
+            {
+                failures.pop_front();
+            }
+            !failures.is_empty()
+        });
+    }
+}
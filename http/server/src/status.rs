@@ -0,0 +1,113 @@
+use crate::imports::*;
+
+/// JSON representation of a monitored node connection.
+#[derive(Serialize)]
+pub struct ConnectionStatus {
+    pub uid: String,
+    pub sid: String,
+    pub service: String,
+    pub transport: String,
+    pub network: String,
+    pub fqdn: String,
+    pub url: String,
+    pub status: &'static str,
+    pub connected: bool,
+    pub online: bool,
+    pub synced: bool,
+    pub clients: u64,
+    pub peers: u64,
+    pub caps: Option<Arc<Caps>>,
+    // uids of the delegate chain, ending with the primary connection
+    pub delegates: Vec<String>,
+}
+
+impl From<&Arc<Connection>> for ConnectionStatus {
+    fn from(connection: &Arc<Connection>) -> Self {
+        let node = connection.node();
+        Self {
+            uid: node.uid_as_str().to_string(),
+            sid: format!("{:016x}", connection.sid()),
+            service: node.service().to_string(),
+            transport: node.transport_kind().to_string(),
+            network: connection.network_id().to_string(),
+            fqdn: node.fqdn.clone(),
+            url: connection.address().to_string(),
+            status: connection.status(),
+            connected: connection.connected(),
+            online: connection.online(),
+            synced: connection.is_synced(),
+            clients: connection.clients(),
+            peers: connection.peers(),
+            caps: connection.caps(),
+            delegates: connection
+                .resolve_delegates()
+                .iter()
+                .map(|delegate| delegate.node().uid_as_str().to_string())
+                .collect(),
+        }
+    }
+}
+
+/// JSON representation of a machine (system id) hosting one or more nodes.
+#[derive(Serialize)]
+pub struct MachineStatus {
+    pub sid: String,
+    pub fqdn: Vec<String>,
+    pub caps: Option<Arc<Caps>>,
+    pub clients: u64,
+    pub peers: u64,
+    pub nodes: Vec<String>,
+}
+
+impl MachineStatus {
+    /// Group connections by the system id of the machine they are running on.
+    /// Connections whose caps have not been received yet are omitted.
+    pub fn collect(connections: &[Arc<Connection>]) -> Vec<MachineStatus> {
+        let mut machines = AHashMap::<u64, MachineStatus>::new();
+        for connection in connections.iter().filter(|c| c.caps().is_some()) {
+            let machine = machines
+                .entry(connection.sid())
+                .or_insert_with(|| MachineStatus {
+                    sid: format!("{:016x}", connection.sid()),
+                    fqdn: vec![],
+                    caps: connection.caps(),
+                    clients: 0,
+                    peers: 0,
+                    nodes: vec![],
+                });
+
+            let fqdn = &connection.node().fqdn;
+            if !machine.fqdn.contains(fqdn) {
+                machine.fqdn.push(fqdn.clone());
+            }
+            // only primary connections carry client and peer counts
+            if connection.is_delegate() {
+                machine.clients += connection.clients();
+                machine.peers += connection.peers();
+            }
+            machine
+                .nodes
+                .push(connection.node().uid_as_str().to_string());
+        }
+
+        let mut machines = machines.into_values().collect::<Vec<_>>();
+        machines.sort_by(|a, b| a.sid.cmp(&b.sid));
+        machines
+    }
+}
+
+/// Resolver response.
+#[derive(Serialize)]
+pub struct Resolution {
+    pub uid: String,
+    pub url: String,
+}
+
+impl From<&Arc<Connection>> for Resolution {
+    fn from(connection: &Arc<Connection>) -> Self {
+        Self {
+            uid: connection.node().uid_as_str().to_string(),
+            url: connection.address().to_string(),
+        }
+    }
+}
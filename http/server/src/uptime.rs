@@ -0,0 +1,77 @@
+use crate::imports::*;
+use std::fmt::Write;
+
+fn escape(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn time(timestamp: u64) -> String {
+    chrono::DateTime::from_timestamp_millis(timestamp as i64)
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_default()
+}
+
+fn percent(uptime: Option<f64>) -> String {
+    uptime
+        .map(|uptime| format!("{uptime:.3}"))
+        .unwrap_or_default()
+}
+
+fn records(report: &UptimeReport) -> impl Iterator<Item = (&'static str, &Availability)> {
+    let nodes = report.nodes.iter().map(|node| ("node", node));
+    let machines = report.machines.iter().map(|machine| ("machine", machine));
+    nodes.chain(machines)
+}
+
+/// Render uptime percentages of nodes and machines as CSV,
+/// one row per node (by uid) or machine (by sid).
+pub fn availability_csv(report: &UptimeReport) -> String {
+    let mut csv =
+        "kind,id,name,uptime_24h,uptime_7d,uptime_30d,outages_30d,downtime_30d_sec\n".to_string();
+    for (kind, record) in records(report) {
+        let downtime = record
+            .outages
+            .iter()
+            .map(|outage| outage.duration)
+            .sum::<u64>();
+        writeln!(
+            csv,
+            "{kind},{:016x},{},{},{},{},{},{:.3}",
+            record.id,
+            escape(&record.name),
+            percent(record.uptime_24h),
+            percent(record.uptime_7d),
+            percent(record.uptime_30d),
+            record.outages.len(),
+            downtime as f64 / 1000.0,
+        )
+        .unwrap();
+    }
+    csv
+}
+
+/// Render outages of nodes and machines within the last 30 days
+/// as CSV, one row per outage; `end` is empty for ongoing outages.
+pub fn outages_csv(report: &UptimeReport) -> String {
+    let mut csv = "kind,id,name,start,end,duration_sec,cause\n".to_string();
+    for (kind, record) in records(report) {
+        for outage in record.outages.iter() {
+            writeln!(
+                csv,
+                "{kind},{:016x},{},{},{},{:.3},{}",
+                record.id,
+                escape(&record.name),
+                time(outage.start),
+                outage.end.map(time).unwrap_or_default(),
+                outage.duration as f64 / 1000.0,
+                outage.cause,
+            )
+            .unwrap();
+        }
+    }
+    csv
+}
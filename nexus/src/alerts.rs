@@ -0,0 +1,519 @@
+use crate::imports::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AlertKind {
+    /// Node is not responding
+    Offline,
+    /// Node is online but not synced
+    NotSynced,
+    /// Node virtual DAA score is behind the network median by more than `threshold`
+    DaaLag,
+    /// Node active peer count is below `threshold`
+    PeersMin,
+    /// Node file descriptor usage is above `threshold` percent of the fd limit
+    FdUsage,
+}
+
+impl Display for AlertKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            AlertKind::Offline => "offline",
+            AlertKind::NotSynced => "not-synced",
+            AlertKind::DaaLag => "daa-lag",
+            AlertKind::PeersMin => "peers-min",
+            AlertKind::FdUsage => "fd-usage",
+        };
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Severity {
+    #[default]
+    Warning,
+    Critical,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Alert rule declared in the `[alerts]` section of `Monitor.toml`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AlertRule {
+    pub kind: AlertKind,
+    #[serde(default)]
+    pub severity: Severity,
+    pub threshold: Option<f64>,
+    // threshold that must be crossed for a firing alert to resolve
+    pub clear: Option<f64>,
+    #[serde(default)]
+    pub debounce_sec: f64,
+    #[serde(default)]
+    pub resolve_sec: f64,
+}
+
+impl AlertRule {
+    pub fn debounce(&self) -> Duration {
+        Duration::from_secs_f64(self.debounce_sec)
+    }
+
+    pub fn resolve(&self) -> Duration {
+        Duration::from_secs_f64(self.resolve_sec)
+    }
+
+    /// Evaluate the rule condition for the given value. While the alert is
+    /// firing, the `clear` threshold (if any) is used, providing hysteresis.
+    pub fn is_active(&self, value: f64, firing: bool) -> bool {
+        let threshold = if firing {
+            self.clear.or(self.threshold)
+        } else {
+            self.threshold
+        };
+
+        match self.kind {
+            AlertKind::Offline | AlertKind::NotSynced => value > 0.0,
+            AlertKind::DaaLag | AlertKind::FdUsage => {
+                threshold.is_some_and(|threshold| value > threshold)
+            }
+            AlertKind::PeersMin => threshold.is_some_and(|threshold| value < threshold),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AlertState {
+    Firing,
+    Resolved,
+}
+
+impl Display for AlertState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            AlertState::Firing => "firing",
+            AlertState::Resolved => "resolved",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Alert lifecycle event produced when an alert fires or resolves.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AlertEvent {
+    pub kind: AlertKind,
+    pub severity: Severity,
+    pub state: AlertState,
+    pub uid: u64,
+    pub address: String,
+    pub fqdn: String,
+    pub network: NetworkId,
+    pub value: f64,
+    pub threshold: Option<f64>,
+    // unix time in msec
+    pub timestamp: u64,
+    // why an alert was resolved without its condition clearing
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl AlertEvent {
+    fn new(
+        rule: &AlertRule,
+        node: &NodeConfig,
+        state: AlertState,
+        value: f64,
+        timestamp: u64,
+    ) -> Self {
+        Self {
+            kind: rule.kind,
+            severity: rule.severity,
+            state,
+            uid: node.uid(),
+            address: node.address.clone(),
+            fqdn: node.fqdn.clone(),
+            network: node.network,
+            value,
+            threshold: rule.threshold,
+            timestamp,
+            reason: None,
+        }
+    }
+
+    fn with_reason(mut self, reason: &str) -> Self {
+        self.reason = Some(reason.to_string());
+        self
+    }
+}
+
+impl Display for AlertEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}] {} {} - {} ({}) value: {}",
+            self.severity, self.kind, self.state, self.address, self.network, self.value
+        )?;
+        if let Some(threshold) = self.threshold {
+            write!(f, " threshold: {threshold}")?;
+        }
+        if let Some(reason) = self.reason.as_ref() {
+            write!(f, " reason: {reason}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Phase {
+    Idle,
+    // condition active, waiting for the debounce period
+    Pending { since: Instant },
+    Firing,
+    // condition cleared, waiting for the resolve period
+    Resolving { since: Instant },
+}
+
+impl Phase {
+    fn is_firing(&self) -> bool {
+        matches!(self, Phase::Firing | Phase::Resolving { .. })
+    }
+}
+
+/// Advance the alert lifecycle state machine, returning the
+/// new phase and the resulting lifecycle transition, if any.
+fn advance(
+    phase: Phase,
+    active: bool,
+    now: Instant,
+    debounce: Duration,
+    resolve: Duration,
+) -> (Phase, Option<AlertState>) {
+    match (phase, active) {
+        (Phase::Idle, false) => (Phase::Idle, None),
+        (Phase::Idle, true) => advance(
+            Phase::Pending { since: now },
+            active,
+            now,
+            debounce,
+            resolve,
+        ),
+        (Phase::Pending { since }, true) => {
+            if now.duration_since(since) >= debounce {
+                (Phase::Firing, Some(AlertState::Firing))
+            } else {
+                (phase, None)
+            }
+        }
+        (Phase::Pending { .. }, false) => (Phase::Idle, None),
+        (Phase::Firing, true) => (Phase::Firing, None),
+        (Phase::Firing, false) => advance(
+            Phase::Resolving { since: now },
+            active,
+            now,
+            debounce,
+            resolve,
+        ),
+        (Phase::Resolving { .. }, true) => (Phase::Firing, None),
+        (Phase::Resolving { since }, false) => {
+            if now.duration_since(since) >= resolve {
+                (Phase::Idle, Some(AlertState::Resolved))
+            } else {
+                (phase, None)
+            }
+        }
+    }
+}
+
+fn median(mut values: Vec<u64>) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    Some(values[values.len() / 2])
+}
+
+/// Alerting engine evaluating [`AlertRule`]s over node connection state
+/// and status metrics. Alert lifecycle events are logged and broadcast
+/// to notifier backends via [`Alerts::multiplexer`].
+pub struct Alerts {
+    rules: Vec<AlertRule>,
+    // alert phase and node for each (rule index, node uid)
+    phases: Mutex<AHashMap<(usize, u64), (Phase, Arc<NodeConfig>)>>,
+    multiplexer: Multiplexer<AlertEvent>,
+}
+
+impl Default for Alerts {
+    fn default() -> Self {
+        Self::new(AlertSettings::rules().to_vec())
+    }
+}
+
+impl Alerts {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        Self {
+            rules,
+            phases: Mutex::new(AHashMap::new()),
+            multiplexer: Multiplexer::new(),
+        }
+    }
+
+    pub fn multiplexer(&self) -> &Multiplexer<AlertEvent> {
+        &self.multiplexer
+    }
+
+    /// Whether the rule is evaluated for the given connection. Rules are
+    /// evaluated per machine, on its delegate connection only (delegators
+    /// mirror the state of their delegate). Rules other than
+    /// [`AlertKind::Offline`] are suspended while the node is offline.
+    fn is_evaluated(rule: &AlertRule, connection: &Arc<Connection>) -> bool {
+        connection.is_delegate() && (rule.kind == AlertKind::Offline || connection.online())
+    }
+
+    /// Value observed by the rule for the given connection;
+    /// `None` if data is unavailable.
+    fn observe(
+        rule: &AlertRule,
+        connection: &Arc<Connection>,
+        network_daa: &AHashMap<NetworkId, u64>,
+    ) -> Option<f64> {
+        let as_value = |flag: bool| if flag { 1.0 } else { 0.0 };
+
+        match rule.kind {
+            AlertKind::Offline => Some(as_value(!connection.online())),
+            AlertKind::NotSynced => Some(as_value(!connection.is_synced())),
+            AlertKind::DaaLag => {
+                let daa_score = connection.last_status()?.virtual_daa_score()?;
+                let median = network_daa.get(&connection.network_id())?;
+                Some(median.saturating_sub(daa_score) as f64)
+            }
+            AlertKind::PeersMin => connection.last_status()?.metric(&Metric::NodeActivePeers),
+            AlertKind::FdUsage => {
+                let fd_limit = connection.caps()?.fd_limit;
+                let fd_count = connection
+                    .last_status()?
+                    .metric(&Metric::NodeFileHandlesCount)?;
+                (fd_limit > 0).then(|| fd_count / fd_limit as f64 * 100.0)
+            }
+        }
+    }
+
+    /// Evaluate all rules against the given connections,
+    /// returning the resulting alert lifecycle events.
+    pub fn evaluate(&self, connections: &[Arc<Connection>]) -> Vec<AlertEvent> {
+        let now = Instant::now();
+        let timestamp = unixtime_as_millis_f64() as u64;
+
+        // median virtual DAA score of synced primary connections per network
+        let network_daa = connections
+            .iter()
+            .filter(|connection| connection.is_delegate() && connection.is_synced())
+            .filter_map(|connection| {
+                let daa_score = connection.last_status()?.virtual_daa_score()?;
+                Some((connection.network_id(), daa_score))
+            })
+            .into_group_map()
+            .into_iter()
+            .filter_map(|(network_id, scores)| Some((network_id, median(scores)?)))
+            .collect::<AHashMap<_, _>>();
+
+        let mut events = Vec::new();
+        let mut phases = self.phases.lock().unwrap();
+
+        // discard state of removed nodes, resolving their firing alerts
+        let uids = connections.iter().map(|c| c.uid()).collect::<AHashSet<_>>();
+        phases.retain(|(index, uid), (phase, node)| {
+            if uids.contains(uid) {
+                return true;
+            }
+            if phase.is_firing() {
+                let event = AlertEvent::new(
+                    &self.rules[*index],
+                    node,
+                    AlertState::Resolved,
+                    0.0,
+                    timestamp,
+                );
+                events.push(event.with_reason("node removed"));
+            }
+            false
+        });
+
+        for (index, rule) in self.rules.iter().enumerate() {
+            for connection in connections {
+                let key = (index, connection.uid());
+                let node = connection.node();
+                let phase = phases
+                    .get(&key)
+                    .map(|(phase, _)| *phase)
+                    .unwrap_or(Phase::Idle);
+                let firing = phase.is_firing();
+
+                // suspended rules are resolved, the offline alert supersedes them
+                if !Self::is_evaluated(rule, connection) {
+                    phases.remove(&key);
+                    if firing {
+                        let reason = if connection.online() {
+                            "connection delegated"
+                        } else {
+                            "node offline"
+                        };
+                        let event =
+                            AlertEvent::new(rule, node, AlertState::Resolved, 0.0, timestamp);
+                        events.push(event.with_reason(reason));
+                    }
+                    continue;
+                }
+
+                let Some(value) = Self::observe(rule, connection, &network_daa) else {
+                    continue;
+                };
+
+                let active = rule.is_active(value, firing);
+                let (phase, transition) =
+                    advance(phase, active, now, rule.debounce(), rule.resolve());
+                phases.insert(key, (phase, node.clone()));
+
+                if let Some(state) = transition {
+                    events.push(AlertEvent::new(rule, node, state, value, timestamp));
+                }
+            }
+        }
+
+        events
+    }
+
+    /// Evaluate rules, logging and broadcasting resulting events.
+    pub async fn update(&self, connections: &[Arc<Connection>]) {
+        for event in self.evaluate(connections) {
+            match event.state {
+                AlertState::Firing => log_warn!("Alert: {event}"),
+                AlertState::Resolved => log_info!("Alert: {event}"),
+            }
+
+            self.multiplexer.broadcast(event).await.ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEBOUNCE: Duration = Duration::from_secs(60);
+    const RESOLVE: Duration = Duration::from_secs(30);
+
+    fn rule(kind: AlertKind) -> AlertRule {
+        AlertRule {
+            kind,
+            severity: Severity::Critical,
+            threshold: None,
+            clear: None,
+            debounce_sec: DEBOUNCE.as_secs_f64(),
+            resolve_sec: RESOLVE.as_secs_f64(),
+        }
+    }
+
+    fn node(address: &str) -> Arc<NodeConfig> {
+        let transport = Transport {
+            kind: TransportKind::WrpcBorsh,
+            tls: true,
+            template: String::new(),
+        };
+        NodeConfig::new(
+            &ServiceKind::Kaspa,
+            NetworkId::from_str("mainnet").unwrap(),
+            &transport,
+            "node.example.com",
+            address,
+        )
+    }
+
+    #[test]
+    fn test_alert_fires_after_debounce_and_resolves_after_resolve_period() {
+        let t0 = Instant::now();
+        let step = |phase, active, elapsed| advance(phase, active, t0 + elapsed, DEBOUNCE, RESOLVE);
+
+        let (phase, transition) = step(Phase::Idle, true, Duration::ZERO);
+        assert!(matches!(phase, Phase::Pending { .. }));
+        assert_eq!(transition, None);
+
+        let (phase, transition) = step(phase, true, Duration::from_secs(30));
+        assert!(matches!(phase, Phase::Pending { .. }));
+        assert_eq!(transition, None);
+
+        let (phase, transition) = step(phase, true, Duration::from_secs(60));
+        assert!(matches!(phase, Phase::Firing));
+        assert_eq!(transition, Some(AlertState::Firing));
+
+        let (phase, transition) = step(phase, false, Duration::from_secs(70));
+        assert!(matches!(phase, Phase::Resolving { .. }));
+        assert_eq!(transition, None);
+
+        let (phase, transition) = step(phase, false, Duration::from_secs(100));
+        assert!(matches!(phase, Phase::Idle));
+        assert_eq!(transition, Some(AlertState::Resolved));
+    }
+
+    #[test]
+    fn test_pending_alert_is_discarded_when_condition_clears() {
+        let t0 = Instant::now();
+        let (phase, _) = advance(Phase::Idle, true, t0, DEBOUNCE, RESOLVE);
+        let (phase, transition) = advance(
+            phase,
+            false,
+            t0 + Duration::from_secs(30),
+            DEBOUNCE,
+            RESOLVE,
+        );
+        assert!(matches!(phase, Phase::Idle));
+        assert_eq!(transition, None);
+    }
+
+    #[test]
+    fn test_resolving_alert_fires_again_when_condition_returns() {
+        let t0 = Instant::now();
+        let (phase, _) = advance(Phase::Firing, false, t0, DEBOUNCE, RESOLVE);
+        let (phase, transition) =
+            advance(phase, true, t0 + Duration::from_secs(10), DEBOUNCE, RESOLVE);
+        assert!(matches!(phase, Phase::Firing));
+        assert_eq!(transition, None);
+    }
+
+    #[test]
+    fn test_firing_alert_of_removed_node_is_resolved() {
+        let alerts = Alerts::new(vec![rule(AlertKind::Offline), rule(AlertKind::NotSynced)]);
+        let firing = node("wss://a.example.com");
+        let pending = node("wss://b.example.com");
+        {
+            let mut phases = alerts.phases.lock().unwrap();
+            phases.insert((0, firing.uid()), (Phase::Firing, firing.clone()));
+            phases.insert(
+                (1, pending.uid()),
+                (
+                    Phase::Pending {
+                        since: Instant::now(),
+                    },
+                    pending.clone(),
+                ),
+            );
+        }
+
+        let events = alerts.evaluate(&[]);
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.kind, AlertKind::Offline);
+        assert_eq!(event.state, AlertState::Resolved);
+        assert_eq!(event.uid, firing.uid());
+        assert_eq!(event.reason.as_deref(), Some("node removed"));
+        assert!(alerts.phases.lock().unwrap().is_empty());
+    }
+}
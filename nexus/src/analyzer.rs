@@ -0,0 +1,180 @@
+use crate::imports::*;
+use kaspa_rpc_core::RpcHash;
+use std::collections::hash_map;
+
+struct SinkState {
+    sink: RpcHash,
+    // time at which the sink was last observed to change
+    changed: Instant,
+}
+
+/// Per-network consensus analyzer comparing the state of Kaspa nodes
+/// on the same network. Flags nodes whose pruning point differs from
+/// the majority, nodes whose virtual DAA score lags the network maximum
+/// and nodes whose sink is not advancing while other nodes' sinks are.
+pub struct Analyzer {
+    daa_lag: u64,
+    sink_stall: Duration,
+    sinks: Mutex<AHashMap<u64, SinkState>>,
+    divergences: Mutex<AHashMap<(u64, DivergenceKind), Arc<Divergence>>>,
+}
+
+impl Default for Analyzer {
+    fn default() -> Self {
+        Self::new(AnalyzerSettings::daa_lag(), AnalyzerSettings::sink_stall())
+    }
+}
+
+impl Analyzer {
+    pub fn new(daa_lag: u64, sink_stall: Duration) -> Self {
+        Self {
+            daa_lag,
+            sink_stall,
+            sinks: Mutex::new(AHashMap::new()),
+            divergences: Mutex::new(AHashMap::new()),
+        }
+    }
+
+    /// Currently detected divergences.
+    pub fn divergences(&self) -> Vec<Arc<Divergence>> {
+        self.divergences.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Analyze the given connections, returning [`Update::Divergence`]
+    /// for newly detected divergences and [`Update::Convergence`] for
+    /// divergences that are no longer present.
+    pub fn analyze(&self, connections: &[Arc<Connection>]) -> Vec<Update> {
+        let now = Instant::now();
+        let timestamp = unixtime_as_millis_f64() as u64;
+
+        // statuses of online primary Kaspa node connections
+        let statuses = connections
+            .iter()
+            .filter(|connection| connection.is_delegate() && connection.online())
+            .filter_map(|connection| match connection.last_status().as_deref() {
+                Some(Status::Kaspa(status)) => Some((
+                    status.uid,
+                    status.network_id,
+                    status.pruning_point_hash,
+                    status.virtual_daa_score,
+                    status.sink,
+                )),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut sinks = self.sinks.lock().unwrap();
+        for (uid, _, _, _, sink) in statuses.iter() {
+            match sinks.get(uid) {
+                Some(state) if state.sink == *sink => {}
+                _ => {
+                    sinks.insert(
+                        *uid,
+                        SinkState {
+                            sink: *sink,
+                            changed: now,
+                        },
+                    );
+                }
+            }
+        }
+        let uids = connections.iter().map(|c| c.uid()).collect::<AHashSet<_>>();
+        sinks.retain(|uid, _| uids.contains(uid));
+
+        let mut detected = AHashMap::<(u64, DivergenceKind), (NetworkId, u64)>::new();
+
+        let networks = statuses
+            .iter()
+            .map(|status| (status.1, status))
+            .into_group_map();
+
+        for (network_id, nodes) in networks {
+            // pruning point differing from the (strict) majority
+            let majority = nodes
+                .iter()
+                .map(|(_, _, pruning_point, _, _)| pruning_point)
+                .counts()
+                .into_iter()
+                .max_by_key(|(_, count)| *count)
+                .filter(|(_, count)| *count * 2 > nodes.len())
+                .map(|(pruning_point, _)| *pruning_point);
+
+            if let Some(majority) = majority {
+                for (uid, _, pruning_point, _, _) in nodes.iter() {
+                    if *pruning_point != majority {
+                        detected.insert((*uid, DivergenceKind::PruningPoint), (network_id, 0));
+                    }
+                }
+            }
+
+            // DAA score lagging the network maximum
+            if let Some(max) = nodes.iter().map(|(_, _, _, daa_score, _)| *daa_score).max() {
+                for (uid, _, _, daa_score, _) in nodes.iter() {
+                    let lag = max - daa_score;
+                    if lag > self.daa_lag {
+                        detected.insert((*uid, DivergenceKind::DaaLag), (network_id, lag));
+                    }
+                }
+            }
+
+            // sink not advancing while sinks of other nodes are
+            let stalled = |uid: &u64| {
+                sinks
+                    .get(uid)
+                    .map(|state| now.duration_since(state.changed))
+                    .filter(|elapsed| *elapsed >= self.sink_stall)
+            };
+            if nodes.iter().any(|(uid, ..)| stalled(uid).is_none()) {
+                for (uid, ..) in nodes.iter() {
+                    if let Some(elapsed) = stalled(uid) {
+                        detected.insert(
+                            (*uid, DivergenceKind::SinkStall),
+                            (network_id, elapsed.as_millis() as u64),
+                        );
+                    }
+                }
+            }
+        }
+
+        let analyzed = statuses
+            .iter()
+            .map(|(uid, ..)| *uid)
+            .collect::<AHashSet<_>>();
+
+        let mut updates = Vec::new();
+        let mut divergences = self.divergences.lock().unwrap();
+
+        // divergences of nodes that were not analyzed (offline
+        // or delegators) are retained until the node is removed
+        divergences.retain(|(uid, kind), _| {
+            let retain = if analyzed.contains(uid) {
+                detected.contains_key(&(*uid, *kind))
+            } else {
+                uids.contains(uid)
+            };
+            if !retain {
+                updates.push(Update::Convergence {
+                    uid: *uid,
+                    kind: *kind,
+                });
+            }
+            retain
+        });
+
+        for ((uid, kind), (network_id, value)) in detected {
+            if let hash_map::Entry::Vacant(entry) = divergences.entry((uid, kind)) {
+                let divergence = Arc::new(Divergence {
+                    uid,
+                    network_id,
+                    kind,
+                    value,
+                    since: timestamp,
+                });
+                entry.insert(divergence.clone());
+                updates.push(Update::Divergence { divergence });
+            }
+        }
+
+        updates
+    }
+}
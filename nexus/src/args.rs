@@ -1,6 +1,12 @@
+use std::path::PathBuf;
+
 #[derive(Debug, Clone)]
 pub struct Args {
     pub trace: bool,
     pub debug: bool,
     pub verbose: bool,
+    // node RPC URLs monitored instead of the resolver config
+    pub node_rpc: Vec<String>,
+    // unencrypted config file monitored instead of the resolver config
+    pub config: Option<PathBuf>,
 }
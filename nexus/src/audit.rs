@@ -0,0 +1,75 @@
+use crate::imports::*;
+use std::io::Write;
+
+#[derive(Serialize)]
+struct Record<'a> {
+    // unix time in msec
+    time: u64,
+    client: String,
+    role: Role,
+    action: &'a str,
+    result: String,
+}
+
+/// Log of admin operations performed by wRPC clients. Records are
+/// logged and, if enabled, appended as JSON lines to
+/// [`AuditSettings::path`].
+pub struct AuditLog {
+    file: Mutex<Option<fs::File>>,
+}
+
+impl AuditLog {
+    pub fn new() -> Self {
+        let file = AuditSettings::enable()
+            .then(|| {
+                let path = AuditSettings::path();
+                fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+                    .map_err(|err| log_error!("Audit: unable to open `{}`: {err}", path.display()))
+                    .ok()
+            })
+            .flatten();
+
+        Self {
+            file: Mutex::new(file),
+        }
+    }
+
+    /// Record the `action` performed by the client `ctx` and its result.
+    pub fn record(
+        &self,
+        ctx: &dyn ContextT,
+        action: &str,
+        result: std::result::Result<(), &Error>,
+    ) {
+        let result = match result {
+            Ok(()) => "ok".to_string(),
+            Err(err) => err.to_string(),
+        };
+        log_info!("Audit: {ctx} ({}) {action}: {result}", ctx.role());
+
+        if let Some(file) = self.file.lock().unwrap().as_mut() {
+            let record = Record {
+                time: unixtime_as_millis_f64() as u64,
+                client: ctx.to_string(),
+                role: ctx.role(),
+                action,
+                result,
+            };
+            let line = serde_json::to_string(&record)
+                .map_err(Error::from)
+                .and_then(|json| Ok(writeln!(file, "{json}")?));
+            if let Err(err) = line {
+                log_error!("Audit: unable to write record: {err}");
+            }
+        }
+    }
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,131 @@
+use crate::imports::*;
+use subtle::ConstantTimeEq;
+
+/// Access role of an authenticated wRPC client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Role {
+    /// queries and notifications
+    ReadOnly,
+    /// all operations, including changes to the monitor state
+    Admin,
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Role::ReadOnly => "read-only",
+            Role::Admin => "admin",
+        };
+        f.write_str(s)
+    }
+}
+
+/// wRPC access token.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Token {
+    // name identifying the token holder in logs
+    pub name: Option<String>,
+    pub token: String,
+    pub role: Role,
+}
+
+impl fmt::Debug for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Token")
+            .field("name", &self.name)
+            .field("role", &self.role)
+            .finish()
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({})",
+            self.name.as_deref().unwrap_or("unnamed"),
+            self.role
+        )
+    }
+}
+
+/// Access tokens accepted by the wRPC server, combining tokens
+/// listed in `Monitor.toml` with those of the `.tokens` key file.
+pub struct Auth {
+    tokens: Vec<Token>,
+}
+
+impl Auth {
+    pub fn try_new() -> Result<Self> {
+        let tokens = AuthSettings::tokens()
+            .iter()
+            .cloned()
+            .chain(load_tokens()?)
+            .collect::<Vec<_>>();
+
+        if tokens.is_empty() {
+            return Err(Error::config(
+                "Authentication is enabled but no access tokens are configured",
+            ));
+        }
+
+        Ok(Self { tokens })
+    }
+
+    /// Find the token matching `token`. Tokens are compared in constant
+    /// time and all configured tokens are compared.
+    pub fn authenticate(&self, token: &str) -> Option<&Token> {
+        self.tokens.iter().fold(None, |matched, known| {
+            let is_match: bool = known.token.as_bytes().ct_eq(token.as_bytes()).into();
+            matched.or(is_match.then_some(known))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth() -> Auth {
+        Auth {
+            tokens: vec![
+                Token {
+                    name: Some("dashboard".to_string()),
+                    token: "read-only-token".to_string(),
+                    role: Role::ReadOnly,
+                },
+                Token {
+                    name: Some("operator".to_string()),
+                    token: "admin-token".to_string(),
+                    role: Role::Admin,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_known_token_is_accepted() {
+        let auth = auth();
+        let token = auth.authenticate("admin-token").unwrap();
+        assert_eq!(token.name.as_deref(), Some("operator"));
+    }
+
+    #[test]
+    fn test_unknown_token_is_rejected() {
+        let auth = auth();
+        assert!(auth.authenticate("admin-token-").is_none());
+        assert!(auth.authenticate("admin").is_none());
+        assert!(auth.authenticate("").is_none());
+    }
+
+    #[test]
+    fn test_token_grants_its_role() {
+        let auth = auth();
+        assert_eq!(
+            auth.authenticate("read-only-token").unwrap().role,
+            Role::ReadOnly
+        );
+        assert_eq!(auth.authenticate("admin-token").unwrap().role, Role::Admin);
+    }
+}
@@ -21,9 +21,15 @@ impl Config {
             .map(|nodes| {
                 nodes
                     .into_iter()
-                    .filter_map(|mut node| {
+                    // disabled nodes are retained, they can be enabled at runtime
+                    .map(|mut node| {
+                        node.tls = node.address.starts_with("wss://");
                         node.uid = xxh3_64(node.address.as_bytes());
-                        node.enable.unwrap_or(true).then_some(node).map(Arc::new)
+                        node.uid_string = format!("{:016x}", node.uid);
+                        node.network_node_uid = xxh3_64(
+                            format!("{}{}{}", node.fqdn, node.network, node.tls).as_bytes(),
+                        );
+                        Arc::new(node)
                     })
                     .collect::<Vec<_>>()
             })
@@ -49,43 +55,50 @@ impl Config {
         let transport_dictionary = &config.transports;
 
         for group in groups.iter() {
-            if !group.fqdn.contains('*') {
-                log_error!("Invalid group FQDN: {}", group.fqdn);
-            } else {
-                let Group {
-                    fqdn,
-                    transports,
-                    services,
-                    network,
-                    ..
-                } = group;
-
-                for service in services.iter() {
-                    for (network_id, ids) in network.iter() {
-                        for transport in transports.iter() {
-                            for id in ids {
-                                if let Some(transport) = transport_dictionary.get(transport) {
-                                    let fqdn = fqdn.replace('*', &id.to_lowercase());
-                                    let address =
-                                        transport.make_address(&fqdn, service, network_id);
-                                    let node = NodeConfig::new(
-                                        service,
-                                        *network_id,
-                                        transport,
-                                        fqdn,
-                                        address,
-                                    );
-                                    nodes.push(node);
-                                } else {
-                                    log_error!("Unknown transport: {}", transport);
-                                }
-                            }
+            let Group {
+                fqdn,
+                transports,
+                services,
+                network,
+                bias,
+                ..
+            } = group;
+
+            if !fqdn.contains('*') {
+                return Err(Error::config(format!("Invalid group FQDN: {fqdn}")));
+            }
+
+            for service in services.iter() {
+                for (network_id, ids) in network.iter() {
+                    for transport in transports.iter() {
+                        let Some(transport) = transport_dictionary.get(transport) else {
+                            return Err(Error::config(format!("Unknown transport: {transport}")));
+                        };
+                        for id in ids {
+                            let fqdn = fqdn.replace('*', &id.to_lowercase());
+                            let address = transport.make_address(&fqdn, service, network_id);
+                            let mut node =
+                                NodeConfig::new(service, *network_id, transport, fqdn, address);
+                            Arc::make_mut(&mut node).bias = *bias;
+                            nodes.push(node);
                         }
                     }
                 }
             }
         }
 
+        // a zero bias would make the node win every resolver selection
+        for node in nodes.iter() {
+            if let Some(bias) = node.bias {
+                if !bias.is_finite() || bias <= 0.0 {
+                    return Err(Error::config(format!(
+                        "Invalid bias {bias} for {}: bias must be a positive number",
+                        node.address
+                    )));
+                }
+            }
+        }
+
         Ok(nodes)
     }
 }
@@ -133,6 +146,18 @@ fn key64_file() -> String {
     ".key64".to_string()
 }
 
+fn webhook_secret_file() -> String {
+    ".webhook".to_string()
+}
+
+fn smtp_password_file() -> String {
+    ".smtp".to_string()
+}
+
+fn tokens_file() -> String {
+    ".tokens".to_string()
+}
+
 fn global_config_file() -> String {
     format!("resolver.{VERSION}.bin")
 }
@@ -141,6 +166,14 @@ fn local_config_file() -> String {
     format!("resolver.{VERSION}.toml")
 }
 
+pub fn global_config_path() -> PathBuf {
+    global_config_folder().join(global_config_file())
+}
+
+pub fn local_config_path() -> Option<PathBuf> {
+    local_config_folder().map(|folder| folder.join(local_config_file()))
+}
+
 pub fn load_key() -> Result<Secret> {
     let key_path = global_config_folder().join(key_file());
     if !key_path.exists() {
@@ -154,9 +187,47 @@ pub fn load_key64() -> Result<u64> {
     if !key64_path.exists() {
         return Err(Error::KeyNotFound);
     }
-    Ok(u64::from_be_bytes(
-        fs::read(key64_path)?.try_into().unwrap(),
-    ))
+    let key64 = fs::read(&key64_path)?
+        .try_into()
+        .map_err(|_| Error::config(format!("Invalid key: {}", key64_path.display())))?;
+    Ok(u64::from_be_bytes(key64))
+}
+
+pub fn load_webhook_secret() -> Result<Vec<u8>> {
+    let secret_path = global_config_folder().join(webhook_secret_file());
+    if !secret_path.exists() {
+        return Err(Error::KeyNotFound);
+    }
+    let secret = fs::read_to_string(secret_path)?;
+    Ok(secret.trim().as_bytes().to_vec())
+}
+
+pub fn load_smtp_password() -> Result<String> {
+    let password_path = global_config_folder().join(smtp_password_file());
+    if !password_path.exists() {
+        return Err(Error::KeyNotFound);
+    }
+    let password = fs::read_to_string(password_path)?;
+    Ok(password.trim().to_string())
+}
+
+/// Load wRPC access tokens from the `.tokens` file in the key folder.
+/// Returns an empty list if the file does not exist.
+pub fn load_tokens() -> Result<Vec<Token>> {
+    #[derive(Deserialize)]
+    struct Tokens {
+        #[serde(default)]
+        token: Vec<Token>,
+    }
+
+    let tokens_path = global_config_folder().join(tokens_file());
+    if !tokens_path.exists() {
+        return Ok(vec![]);
+    }
+    let toml = fs::read_to_string(&tokens_path)?;
+    let tokens = toml::from_str::<Tokens>(&toml)
+        .map_err(|err| Error::config(format!("{}: {err}", tokens_path.display())))?;
+    Ok(tokens.token)
 }
 
 pub fn locate_local_config() -> Option<PathBuf> {
@@ -206,6 +277,13 @@ pub fn load_default_config() -> Result<Vec<Arc<NodeConfig>>> {
     Config::try_parse(toml.as_str())
 }
 
+/// Load an unencrypted config file (see `README.md` for the format).
+pub fn load_config_file(path: &Path) -> Result<Vec<Arc<NodeConfig>>> {
+    let toml = fs::read_to_string(path)
+        .map_err(|err| Error::config(format!("Unable to read `{}`: {err}", path.display())))?;
+    Config::try_parse(toml.as_str())
+}
+
 pub async fn update_global_config() -> Result<Option<Vec<Arc<NodeConfig>>>> {
     static HASH: Mutex<Option<Vec<u8>>> = Mutex::new(None);
 
@@ -302,10 +380,21 @@ pub fn get_key() -> Result<Secret> {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Settings {
     updates: Updates,
+    config: ConfigSettings,
     limits: Limits,
     sync: SyncSettings,
     ttl: TtlSettings,
     http: HttpSettings,
+    storage: StorageSettings,
+    alerts: AlertSettings,
+    analyzer: AnalyzerSettings,
+    auth: AuthSettings,
+    audit: AuditSettings,
+    notifications: NotificationSettings,
+    health: HealthSettings,
+    uptime: UptimeSettings,
+    smtp: SmtpSettings,
+    webhook: WebhookSettings,
 }
 
 impl Settings {
@@ -342,6 +431,18 @@ impl Updates {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ConfigSettings {
+    pub poll_sec: f64,
+}
+
+impl ConfigSettings {
+    pub fn poll() -> Duration {
+        Duration::from_secs_f64(Settings::get().config.poll_sec)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Limits {
     pub fd: u64,
@@ -409,6 +510,8 @@ pub struct HttpSettings {
 pub struct HttpStatus {
     pub sessions: Option<usize>,
     pub ttl_hrs: Option<f64>,
+    pub login_attempts: Option<usize>,
+    pub login_lockout_min: Option<f64>,
 }
 
 impl HttpStatus {
@@ -424,4 +527,371 @@ impl HttpStatus {
             .unwrap_or(48.0 * 3600.0);
         Duration::from_secs_f64(ttl_sec)
     }
+    pub fn login_attempts() -> usize {
+        Settings::get().http.status.login_attempts.unwrap_or(5)
+    }
+    pub fn login_lockout() -> Duration {
+        let lockout_min = Settings::get()
+            .http
+            .status
+            .login_lockout_min
+            .unwrap_or(15.0);
+        Duration::from_secs_f64(lockout_min * 60.0)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StorageSettings {
+    pub enable: bool,
+    pub folder: Option<String>,
+    pub interval_sec: f64,
+    pub raw_hrs: f64,
+    pub downsample_min: f64,
+    pub retention_days: f64,
+    pub compaction_min: f64,
+}
+
+impl StorageSettings {
+    pub fn enable() -> bool {
+        Settings::get().storage.enable
+    }
+
+    pub fn folder() -> PathBuf {
+        Settings::get()
+            .storage
+            .folder
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| global_config_folder().join("history"))
+    }
+
+    pub fn interval() -> Duration {
+        Duration::from_secs_f64(Settings::get().storage.interval_sec)
+    }
+
+    pub fn compaction() -> Duration {
+        Duration::from_secs_f64(Settings::get().storage.compaction_min * 60.0)
+    }
+
+    pub fn retention() -> Retention {
+        let storage = &Settings::get().storage;
+        Retention {
+            raw: Duration::from_secs_f64(storage.raw_hrs * 3600.0),
+            downsample: Duration::from_secs_f64(storage.downsample_min * 60.0),
+            history: Duration::from_secs_f64(storage.retention_days * 24.0 * 3600.0),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AlertSettings {
+    pub enable: bool,
+    pub poll_sec: f64,
+    #[serde(rename = "rule", default)]
+    pub rules: Vec<AlertRule>,
+}
+
+impl AlertSettings {
+    pub fn enable() -> bool {
+        Settings::get().alerts.enable
+    }
+
+    pub fn poll() -> Duration {
+        Duration::from_secs_f64(Settings::get().alerts.poll_sec)
+    }
+
+    pub fn rules() -> &'static [AlertRule] {
+        Settings::get().alerts.rules.as_slice()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AnalyzerSettings {
+    pub enable: bool,
+    pub poll_sec: f64,
+    pub daa_lag: u64,
+    pub sink_stall_sec: f64,
+}
+
+impl AnalyzerSettings {
+    pub fn enable() -> bool {
+        Settings::get().analyzer.enable
+    }
+
+    pub fn poll() -> Duration {
+        Duration::from_secs_f64(Settings::get().analyzer.poll_sec)
+    }
+
+    pub fn daa_lag() -> u64 {
+        Settings::get().analyzer.daa_lag
+    }
+
+    pub fn sink_stall() -> Duration {
+        Duration::from_secs_f64(Settings::get().analyzer.sink_stall_sec)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuthSettings {
+    pub enable: bool,
+    pub handshake_timeout_sec: f64,
+    #[serde(default)]
+    pub token: Vec<Token>,
+}
+
+impl AuthSettings {
+    pub fn enable() -> bool {
+        Settings::get().auth.enable
+    }
+
+    pub fn handshake_timeout() -> Duration {
+        Duration::from_secs_f64(Settings::get().auth.handshake_timeout_sec)
+    }
+
+    pub fn tokens() -> &'static [Token] {
+        &Settings::get().auth.token
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuditSettings {
+    pub enable: bool,
+    pub path: Option<String>,
+}
+
+impl AuditSettings {
+    pub fn enable() -> bool {
+        Settings::get().audit.enable
+    }
+
+    pub fn path() -> PathBuf {
+        Settings::get()
+            .audit
+            .path
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| global_config_folder().join("audit.log"))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NotificationSettings {
+    pub queue_size: usize,
+    pub max_lag_sec: f64,
+}
+
+impl NotificationSettings {
+    pub fn queue_size() -> usize {
+        Settings::get().notifications.queue_size
+    }
+
+    pub fn max_lag() -> Duration {
+        Duration::from_secs_f64(Settings::get().notifications.max_lag_sec)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HealthSettings {
+    pub window: usize,
+    pub latency_target_msec: f64,
+    pub notify_sec: f64,
+}
+
+impl HealthSettings {
+    pub fn window() -> usize {
+        Settings::get().health.window
+    }
+
+    pub fn latency_target() -> f64 {
+        Settings::get().health.latency_target_msec
+    }
+
+    pub fn notify() -> Duration {
+        Duration::from_secs_f64(Settings::get().health.notify_sec)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UptimeSettings {
+    pub enable: bool,
+    pub path: Option<String>,
+    pub min_outage_sec: f64,
+    pub save_sec: f64,
+    pub retention_days: f64,
+}
+
+impl UptimeSettings {
+    pub fn enable() -> bool {
+        Settings::get().uptime.enable
+    }
+
+    pub fn path() -> PathBuf {
+        Settings::get()
+            .uptime
+            .path
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| global_config_folder().join("uptime.json"))
+    }
+
+    pub fn min_outage() -> Duration {
+        Duration::from_secs_f64(Settings::get().uptime.min_outage_sec)
+    }
+
+    pub fn save() -> Duration {
+        Duration::from_secs_f64(Settings::get().uptime.save_sec)
+    }
+
+    pub fn retention() -> Duration {
+        Duration::from_secs_f64(Settings::get().uptime.retention_days * 24.0 * 60.0 * 60.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SmtpSecurity {
+    Tls,
+    Starttls,
+    None,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SmtpSettings {
+    pub enable: bool,
+    pub host: String,
+    pub port: u16,
+    pub security: SmtpSecurity,
+    pub username: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+    pub batch_sec: f64,
+    pub max_per_hour: usize,
+}
+
+impl SmtpSettings {
+    pub fn get() -> &'static SmtpSettings {
+        &Settings::get().smtp
+    }
+
+    pub fn enable() -> bool {
+        Settings::get().smtp.enable
+    }
+
+    pub fn batch(&self) -> Duration {
+        Duration::from_secs_f64(self.batch_sec)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WebhookSettings {
+    pub enable: bool,
+    pub urls: Vec<String>,
+    pub sign: bool,
+    pub timeout_sec: f64,
+    pub retry_min_sec: f64,
+    pub retry_max_sec: f64,
+    pub queue_size: usize,
+}
+
+impl WebhookSettings {
+    pub fn get() -> &'static WebhookSettings {
+        &Settings::get().webhook
+    }
+
+    pub fn enable() -> bool {
+        Settings::get().webhook.enable
+    }
+
+    pub fn timeout() -> Duration {
+        Duration::from_secs_f64(Settings::get().webhook.timeout_sec)
+    }
+
+    /// Delay before the delivery attempt following `attempts` failed attempts.
+    pub fn backoff(attempts: u32) -> Duration {
+        let webhook = &Settings::get().webhook;
+        let delay = webhook.retry_min_sec * 2f64.powi(attempts.min(32) as i32);
+        Duration::from_secs_f64(delay.min(webhook.retry_max_sec))
+    }
+
+    pub fn queue_file() -> PathBuf {
+        global_config_folder().join("webhook.queue.json")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSPORT: &str = r#"
+        [transport.wrpc-borsh]
+        type = "wrpc-borsh"
+        tls = true
+        template = "wss://${fqdn}/${service}/${network}/${protocol}/${encoding}"
+    "#;
+
+    fn node_config(bias: &str) -> String {
+        format!(
+            r#"
+            {TRANSPORT}
+            [[node]]
+            service = "kaspa"
+            address = "wss://node.example.com/kaspa/mainnet/wrpc/borsh"
+            transport-type = "wrpc-borsh"
+            network = "mainnet"
+            fqdn = "node.example.com"
+            {bias}
+            "#
+        )
+    }
+
+    fn group_config(bias: &str) -> String {
+        format!(
+            r#"
+            {TRANSPORT}
+            [[group]]
+            fqdn = "*.example.com"
+            transports = ["wrpc-borsh"]
+            services = ["kaspa"]
+            {bias}
+
+            [group.network]
+            mainnet = ["a", "b"]
+            "#
+        )
+    }
+
+    #[test]
+    fn test_positive_bias_is_accepted() {
+        let nodes = Config::try_parse(&node_config("bias = 2.0")).unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].bias(), 2 * BIAS_SCALE);
+
+        let nodes = Config::try_parse(&group_config("bias = 0.5")).unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert!(nodes.iter().all(|node| node.bias() == BIAS_SCALE / 2));
+    }
+
+    #[test]
+    fn test_missing_bias_defaults_to_one() {
+        let nodes = Config::try_parse(&node_config("")).unwrap();
+        assert_eq!(nodes[0].bias(), BIAS_SCALE);
+    }
+
+    #[test]
+    fn test_non_positive_bias_is_rejected() {
+        for bias in ["bias = 0.0", "bias = -1.0", "bias = nan"] {
+            assert!(Config::try_parse(&node_config(bias)).is_err(), "{bias}");
+            assert!(Config::try_parse(&group_config(bias)).is_err(), "{bias}");
+        }
+    }
 }
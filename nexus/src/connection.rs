@@ -1,6 +1,5 @@
 use crate::imports::*;
 
-#[allow(dead_code)]
 pub const BIAS_SCALE: u64 = 1_000_000;
 
 impl fmt::Display for Connection {
@@ -16,10 +15,20 @@ impl fmt::Display for Connection {
     }
 }
 
+/// Connection state reported to clients with [`Update::State`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct State {
+    connected: bool,
+    online: bool,
+    synced: bool,
+    delegate: Option<u64>,
+}
+
 #[derive(Debug)]
 pub struct Connection {
     args: Arc<Args>,
     caps: ArcSwapOption<Caps>,
+    last_status: ArcSwapOption<Status>,
     is_synced: AtomicBool,
     sid: AtomicU64,
     clients: AtomicU64,
@@ -31,6 +40,10 @@ pub struct Connection {
     delegate: ArcSwap<Option<Arc<Connection>>>,
     is_connected: AtomicBool,
     is_online: AtomicBool,
+    // last reported state and the time (unix msec) it was entered
+    state: Mutex<State>,
+    since: AtomicU64,
+    health: HealthTracker,
     sender: Sender<Event>,
 }
 
@@ -41,23 +54,33 @@ impl Connection {
         node: Arc<NodeConfig>,
         sender: Sender<Event>,
     ) -> Result<Self> {
-        let client = match node.transport_kind {
-            TransportKind::WrpcBorsh => {
-                rpc::kaspa::Client::try_new(WrpcEncoding::Borsh, &node.address)?
+        let client = match (node.service(), node.transport_kind) {
+            (ServiceKind::Kaspa, TransportKind::WrpcBorsh) => rpc::Client::from(
+                rpc::kaspa::Client::try_new(WrpcEncoding::Borsh, &node.address)?,
+            ),
+            (ServiceKind::Kaspa, TransportKind::WrpcJson) => rpc::Client::from(
+                rpc::kaspa::Client::try_new(WrpcEncoding::SerdeJson, &node.address)?,
+            ),
+            (ServiceKind::Kaspa, TransportKind::Grpc) => {
+                rpc::Client::from(rpc::grpc::Client::try_new(&node.address)?)
             }
-            TransportKind::WrpcJson => {
-                rpc::kaspa::Client::try_new(WrpcEncoding::SerdeJson, &node.address)?
+            (ServiceKind::Sparkle, TransportKind::WrpcBorsh) => {
+                rpc::Client::from(rpc::sparkle::Client::try_new(WrpcEncoding::Borsh, &node)?)
             }
-            TransportKind::Grpc => {
-                unimplemented!("gRPC support is not currently implemented")
+            (ServiceKind::Sparkle, TransportKind::WrpcJson) => rpc::Client::from(
+                rpc::sparkle::Client::try_new(WrpcEncoding::SerdeJson, &node)?,
+            ),
+            (ServiceKind::Sparkle, TransportKind::Grpc) => {
+                return Err(Error::config(format!(
+                    "gRPC transport is not supported by Sparkle: {node}"
+                )));
             }
         };
 
-        let client = rpc::Client::from(client);
-
         Ok(Self {
             args: args.clone(),
             caps: ArcSwapOption::new(None),
+            last_status: ArcSwapOption::new(None),
             monitor,
             node,
             client,
@@ -69,6 +92,9 @@ impl Connection {
             clients: AtomicU64::new(0),
             peers: AtomicU64::new(0),
             is_online: AtomicBool::new(false),
+            state: Mutex::new(State::default()),
+            since: AtomicU64::new(unixtime_as_millis_f64() as u64),
+            health: HealthTracker::default(),
             sender,
         })
     }
@@ -78,9 +104,21 @@ impl Connection {
         self.args.verbose
     }
 
+    /// Number of clients of the node weighted by the node bias and
+    /// scaled by [`BIAS_SCALE`], used by the resolver (lower is better).
+    /// The score of nodes whose p95 RPC latency exceeds
+    /// [`HealthSettings::latency_target`] is raised proportionally.
+    /// Both the client count and the latency are taken from the
+    /// delegate connection to the node.
     #[inline]
-    pub fn score(&self) -> u64 {
-        self.clients.load(Ordering::Relaxed)
+    pub fn score(self: &Arc<Self>) -> u64 {
+        let delegate = self.delegate();
+        let latency = delegate
+            .health
+            .latency()
+            .map(|latency| (latency / HealthSettings::latency_target()).max(1.0))
+            .unwrap_or(1.0);
+        (((delegate.clients() + 1) * self.node.bias()) as f64 * latency) as u64
     }
 
     #[inline]
@@ -129,11 +167,28 @@ impl Connection {
         self.caps.load().clone()
     }
 
+    /// RPC health statistics of this connection.
+    pub fn health(&self) -> Health {
+        self.health.health(self.uid())
+    }
+
+    /// Most recent status received from the node.
+    #[inline]
+    pub fn last_status(&self) -> Option<Arc<Status>> {
+        self.last_status.load().clone()
+    }
+
     #[inline]
     pub fn uid(&self) -> u64 {
         self.node.uid()
     }
 
+    /// Time (unix msec) at which the connection entered its current state.
+    #[inline]
+    pub fn since(&self) -> u64 {
+        self.since.load(Ordering::Relaxed)
+    }
+
     #[inline]
     pub fn sid(&self) -> u64 {
         self.sid.load(Ordering::Relaxed)
@@ -177,6 +232,19 @@ impl Connection {
         self.delegate.store(Arc::new(delegate));
     }
 
+    /// Connection this connection is directly bound to, if any.
+    #[inline]
+    pub fn bound_delegate(&self) -> Option<Arc<Connection>> {
+        (**self.delegate.load()).clone()
+    }
+
+    /// Clear the delegate binding and caps so that the
+    /// delegate is resolved again on the next state update.
+    pub fn reset_delegate(&self) {
+        self.caps.store(None);
+        self.bind_delegate(None);
+    }
+
     pub fn resolve_delegates(self: &Arc<Self>) -> Vec<Arc<Connection>> {
         let mut delegates = Vec::new();
         let mut delegate = (*self).clone();
@@ -187,6 +255,27 @@ impl Connection {
         delegates
     }
 
+    /// Current state of this connection as presented to monitor clients.
+    pub fn snapshot(self: &Arc<Self>) -> ConnectionSnapshot {
+        ConnectionSnapshot {
+            node: Arc::new(self.node.info()),
+            sid: self.sid(),
+            connected: self.connected(),
+            online: self.online(),
+            synced: self.is_synced(),
+            delegator: !self.is_delegate(),
+            delegates: self
+                .resolve_delegates()
+                .iter()
+                .map(|delegate| delegate.uid())
+                .collect(),
+            caps: self.caps(),
+            status: self.last_status(),
+            since: self.since(),
+            health: Some(Arc::new(self.health())),
+        }
+    }
+
     pub fn status(&self) -> &'static str {
         if self.connected() {
             if !self.is_delegate() {
@@ -201,6 +290,46 @@ impl Connection {
         }
     }
 
+    /// Post [`Event::State`] if the connection state has changed since it was last posted.
+    async fn post_state(&self) -> Result<()> {
+        let state = State {
+            connected: self.connected(),
+            online: self.online(),
+            synced: self.is_synced(),
+            delegate: self.bound_delegate().map(|delegate| delegate.uid()),
+        };
+
+        {
+            let mut last = self.state.lock().unwrap();
+            if *last == state {
+                return Ok(());
+            }
+            *last = state;
+        }
+
+        let since = unixtime_as_millis_f64() as u64;
+        self.since.store(since, Ordering::Relaxed);
+
+        let State {
+            connected,
+            online,
+            synced,
+            delegate,
+        } = state;
+        self.sender
+            .send(Event::State {
+                uid: self.uid(),
+                connected,
+                online,
+                synced,
+                delegate,
+                since,
+            })
+            .await?;
+
+        Ok(())
+    }
+
     async fn connect(&self) -> Result<()> {
         self.client.connect().await?;
         Ok(())
@@ -223,6 +352,8 @@ impl Connection {
             interval(SyncSettings::ping())
         };
 
+        let mut health = interval(HealthSettings::notify());
+
         let mut last_connect_time: Option<Instant> = None;
 
         // use futures::StreamExt;
@@ -261,6 +392,13 @@ impl Connection {
                             }
                         }
                     }
+
+                    self.post_state().await.ok();
+                }
+
+                _ = health.next().fuse() => {
+                    let health = Arc::new(self.health());
+                    self.sender.send(Event::Health { health }).await.ok();
                 }
 
                 msg = rpc_ctl_channel.receiver.recv().fuse() => {
@@ -278,17 +416,20 @@ impl Connection {
                                         log_info!("Connected: {}",self.node.address);
                                     }
                                     self.is_connected.store(true, Ordering::Relaxed);
+                                    self.health.connected();
                                     if self.update_state().await.is_ok() {
                                         self.is_online.store(true, Ordering::Relaxed);
                                     } else {
                                         self.is_online.store(false, Ordering::Relaxed);
                                     }
+                                    self.post_state().await.ok();
                                 },
                                 Ctl::Disconnect => {
                                     self.is_connected.store(false, Ordering::Relaxed);
                                     self.is_online.store(false, Ordering::Relaxed);
                                     last_connect_time = None;
                                     log_error!("Disconnected: {}",self.node.address);
+                                    self.post_state().await.ok();
                                 }
                             }
                         }
@@ -330,16 +471,41 @@ impl Connection {
         Ok(())
     }
 
+    /// Drop and re-establish the RPC connection, re-fetching node caps.
+    pub async fn reconnect(&self) -> Result<()> {
+        self.caps.store(None);
+        self.client.disconnect().await?;
+        self.client.connect().await?;
+        Ok(())
+    }
+
+    /// Abort the RPC connection, triggering the client reconnection sequence.
+    pub fn trigger_abort(&self) -> Result<()> {
+        self.client.trigger_abort()
+    }
+
     async fn update_state(self: &Arc<Self>) -> Result<()> {
         if !self.is_delegate() {
-            if let Err(err) = self.client.ping().await {
+            if let Err(err) = self
+                .health
+                .measure(RpcMethod::Ping, self.client.ping())
+                .await
+            {
                 log_error!("Ping: {err}");
             }
+            // delegators share the node process of their delegate,
+            // whose metrics account for connections of all encodings
+            let delegate = self.delegate();
+            self.clients.store(delegate.clients(), Ordering::Relaxed);
+            self.peers.store(delegate.peers(), Ordering::Relaxed);
             return Ok(());
         }
 
         if self.caps().is_none() {
-            let caps = self.client.get_caps().await?;
+            let caps = self
+                .health
+                .measure(RpcMethod::GetCaps, self.client.get_caps())
+                .await?;
             let sid = caps.system_id();
             self.sid.store(sid, Ordering::Relaxed);
             let delegate_key = Delegate::new(sid, self.network_id());
@@ -353,19 +519,26 @@ impl Connection {
             }
         }
 
-        match self.client.get_sync().await {
+        match self
+            .health
+            .measure(RpcMethod::GetSync, self.client.get_sync())
+            .await
+        {
             Ok(is_synced) => {
                 let _previous_sync = self.is_synced.load(Ordering::Relaxed);
                 self.is_synced.store(is_synced, Ordering::Relaxed);
 
-                match self.client.get_status(self).await {
+                match self
+                    .health
+                    .measure(RpcMethod::GetStatus, self.client.get_status(self))
+                    .await
+                {
                     Ok(status) => {
-                        self.sender
-                            .send(Event::Status {
-                                status: Arc::new(status),
-                            })
-                            .await
-                            .unwrap();
+                        let status = Arc::new(status);
+                        self.clients.store(status.clients(), Ordering::Relaxed);
+                        self.peers.store(status.peers(), Ordering::Relaxed);
+                        self.last_status.store(Some(status.clone()));
+                        self.sender.send(Event::Status { status }).await.unwrap();
                         Ok(())
                     }
                     Err(err) => {
@@ -383,3 +556,25 @@ impl Connection {
         }
     }
 }
+
+#[cfg(test)]
+impl Connection {
+    /// Set the state otherwise reported by the node, `clients_limit`
+    /// replacing the node caps.
+    pub(crate) fn set_state(&self, online: bool, synced: bool, clients: u64, clients_limit: u64) {
+        self.is_connected.store(online, Ordering::Relaxed);
+        self.is_online.store(online, Ordering::Relaxed);
+        self.is_synced.store(synced, Ordering::Relaxed);
+        self.clients.store(clients, Ordering::Relaxed);
+        self.caps.store(Some(Arc::new(Caps {
+            version: String::new(),
+            system_id: 0,
+            git_hash: None,
+            total_memory: 0,
+            resident_set_size: None,
+            cpu_physical_cores: 1,
+            fd_limit: 1024,
+            clients_limit,
+        })));
+    }
+}
@@ -1,7 +1,11 @@
 use crate::imports::*;
 
 #[async_trait]
-pub trait ContextT: Send + Sync {
+pub trait ContextT: Display + Send + Sync {
     fn id(&self) -> u64;
+    /// Role granted to the client during the handshake.
+    fn role(&self) -> Role;
     async fn notify(&self, notification: Notification) -> Result<()>;
+    /// Close the client connection.
+    fn disconnect(&self) -> Result<()>;
 }
@@ -42,6 +42,9 @@ pub enum Error {
     #[error(transparent)]
     KaspaRpc(#[from] kaspa_wrpc_client::error::Error),
 
+    #[error(transparent)]
+    KaspaGrpc(#[from] kaspa_grpc_client::error::Error),
+
     #[error(transparent)]
     SparkleRpc(#[from] sparkle_rpc_client::error::Error),
 
@@ -54,6 +57,9 @@ pub enum Error {
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
 
+    #[error("Not connected")]
+    NotConnected,
+
     #[error("Status")]
     Status,
 
@@ -69,6 +75,9 @@ pub enum Error {
     #[error("TOML error: {0}")]
     Toml(#[from] toml::de::Error),
 
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+
     #[error("Configuration error")]
     Config(String),
 
@@ -80,6 +89,30 @@ pub enum Error {
 
     #[error(transparent)]
     KaspaMetrics(#[from] kaspa_metrics_core::error::Error),
+
+    #[error(transparent)]
+    Database(#[from] kaspa_monitor_database::error::Error),
+
+    #[error(transparent)]
+    Smtp(#[from] mail_send::Error),
+
+    #[error("Status history storage is not enabled")]
+    StorageDisabled,
+
+    #[error("Uptime tracking is not enabled")]
+    UptimeDisabled,
+
+    #[error("Operation requires the `{0}` role")]
+    Unauthorized(crate::auth::Role),
+
+    #[error("Node not found: {0:016x}")]
+    NodeNotFound(u64),
+
+    #[error("No nodes available")]
+    NoNodesAvailable,
+
+    #[error("Task join error: {0}")]
+    Join(#[from] tokio::task::JoinError),
 }
 
 impl Error {
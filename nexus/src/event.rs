@@ -4,6 +4,22 @@ use crate::imports::*;
 pub enum Event {
     Start,
     Update,
-    Status { status: Arc<Status> },
-    Caps { uid: u64, caps: Arc<Caps> },
+    Status {
+        status: Arc<Status>,
+    },
+    Caps {
+        uid: u64,
+        caps: Arc<Caps>,
+    },
+    State {
+        uid: u64,
+        connected: bool,
+        online: bool,
+        synced: bool,
+        delegate: Option<u64>,
+        since: u64,
+    },
+    Health {
+        health: Arc<Health>,
+    },
 }
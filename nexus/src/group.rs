@@ -7,6 +7,8 @@ pub struct Group {
     pub transports: Vec<String>,
     pub services: Vec<ServiceKind>,
     pub network: HashMap<NetworkId, Vec<String>>,
+    // resolver bias applied to all nodes of the group
+    pub bias: Option<f64>,
 }
 
 impl std::fmt::Display for Group {
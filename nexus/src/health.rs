@@ -0,0 +1,97 @@
+use crate::imports::*;
+
+#[derive(Default)]
+struct Inner {
+    // most recent round-trip times in msec, oldest first
+    latencies: VecDeque<f64>,
+    errors: AHashMap<RpcMethod, u64>,
+    connects: u64,
+    last_success: Option<u64>,
+    last_failure: Option<u64>,
+}
+
+/// RPC health statistics of a node connection: round-trip latency of
+/// the last [`HealthSettings::window`] calls, failed calls by method,
+/// reconnections and the time of the last successful and failed calls.
+#[derive(Default)]
+pub struct HealthTracker {
+    inner: Mutex<Inner>,
+}
+
+impl fmt::Debug for HealthTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HealthTracker").finish_non_exhaustive()
+    }
+}
+
+impl HealthTracker {
+    /// Await `future` performing the RPC `method`, recording
+    /// its round-trip time on success or the failure otherwise.
+    pub async fn measure<T, E>(
+        &self,
+        method: RpcMethod,
+        future: impl Future<Output = std::result::Result<T, E>>,
+    ) -> std::result::Result<T, E> {
+        let start = Instant::now();
+        let result = future.await;
+        let elapsed = start.elapsed().as_secs_f64() * 1000.0;
+        let now = unixtime_as_millis_f64() as u64;
+
+        let mut inner = self.inner.lock().unwrap();
+        if result.is_ok() {
+            inner.latencies.push_back(elapsed);
+            while inner.latencies.len() > HealthSettings::window() {
+                inner.latencies.pop_front();
+            }
+            inner.last_success = Some(now);
+        } else {
+            *inner.errors.entry(method).or_default() += 1;
+            inner.last_failure = Some(now);
+        }
+
+        result
+    }
+
+    /// Record an established RPC connection.
+    pub fn connected(&self) {
+        self.inner.lock().unwrap().connects += 1;
+    }
+
+    /// 95th percentile round-trip time in msec (`None` if no calls succeeded).
+    pub fn latency(&self) -> Option<f64> {
+        let inner = self.inner.lock().unwrap();
+        let mut latencies = inner.latencies.iter().copied().collect::<Vec<_>>();
+        latencies.sort_by(f64::total_cmp);
+        percentile(&latencies, 0.95)
+    }
+
+    pub fn health(&self, uid: u64) -> Health {
+        let inner = self.inner.lock().unwrap();
+        let mut latencies = inner.latencies.iter().copied().collect::<Vec<_>>();
+        latencies.sort_by(f64::total_cmp);
+        let errors = inner
+            .errors
+            .iter()
+            .map(|(method, count)| (*method, *count))
+            .sorted_by_key(|(method, _)| *method as u8)
+            .collect();
+
+        Health {
+            uid,
+            samples: latencies.len() as u64,
+            p50: percentile(&latencies, 0.50).unwrap_or_default(),
+            p95: percentile(&latencies, 0.95).unwrap_or_default(),
+            p99: percentile(&latencies, 0.99).unwrap_or_default(),
+            errors,
+            reconnects: inner.connects.saturating_sub(1),
+            last_success: inner.last_success,
+            last_failure: inner.last_failure,
+        }
+    }
+}
+
+/// Nearest-rank percentile of ascending `samples`.
+fn percentile(samples: &[f64], p: f64) -> Option<f64> {
+    let rank = (p * samples.len() as f64).ceil() as usize;
+    samples.get(rank.saturating_sub(1)).copied()
+}
@@ -43,7 +43,7 @@ pub use workflow_log::prelude::*;
 pub use workflow_rpc::client::{ConnectOptions, ConnectStrategy, Ctl};
 pub use workflow_rpc::encoding::Encoding as WrpcEncoding;
 
-pub use kaspa_consensus_core::network::NetworkId;
+pub use kaspa_consensus_core::network::{NetworkId, NetworkType};
 pub use kaspa_utils::hex::*;
 
 pub use kaspa_consensus_core::tx::{PopulatedTransaction, Transaction, VerifiableTransaction};
@@ -52,6 +52,7 @@ pub use kaspa_metrics_core::{MetricsData, MetricsSnapshot};
 pub use kaspa_monitor_core::caps::Caps;
 pub use kaspa_monitor_core::runtime::{Runtime, Service, ServiceError, ServiceResult};
 pub use kaspa_monitor_core::status::*;
+pub use kaspa_monitor_database::prelude::{Database, Retention, Sample};
 pub use kaspa_monitor_rpc_core::prelude::*;
 
 pub use crate::error::Error;
@@ -60,16 +61,29 @@ pub use crate::nexus::Nexus;
 pub use crate::result::Result;
 pub use crate::utils::*;
 
+pub use crate::alerts::*;
+pub use crate::analyzer::Analyzer;
 pub use crate::args::*;
+pub use crate::audit::AuditLog;
+pub use crate::auth::{Auth, Role, Token};
 pub use crate::config::*;
-pub use crate::connection::Connection;
+pub use crate::connection::{Connection, BIAS_SCALE};
 pub use crate::context::*;
 pub use crate::delegate::*;
 pub use crate::group::*;
-pub use crate::monitor::Monitor;
+pub use crate::health::HealthTracker;
+pub use crate::monitor::{Monitor, Reconciliation};
 pub use crate::node::*;
+pub use crate::outbox::{NotificationStats, Outbox};
+pub use crate::resolver::{self, Target};
 pub(crate) use crate::rpc;
 pub use crate::rpc::ClientT;
 pub use crate::services::ServiceKind;
+pub use crate::smtp::SmtpNotifier;
+pub use crate::storage::Storage;
+pub use crate::subscription::Subscription;
 pub use crate::tpl::Tpl;
 pub use crate::transport::*;
+pub use crate::uptime::Uptime;
+pub use crate::watcher::{ConfigDiff, ConfigWatcher};
+pub use crate::webhook::WebhookNotifier;
@@ -4,6 +4,10 @@ cfg_if::cfg_if! {
         #[allow(clippy::module_inception)]
         pub mod nexus;
 
+        pub mod alerts;
+        pub mod analyzer;
+        pub mod audit;
+        pub mod auth;
         pub mod error;
         pub mod args;
         pub mod context;
@@ -15,11 +19,20 @@ cfg_if::cfg_if! {
         pub mod connection;
         pub mod delegate;
         pub mod group;
+        pub mod health;
         pub mod node;
+        pub mod resolver;
+        pub mod outbox;
         pub mod services;
+        pub mod smtp;
+        pub mod storage;
+        pub mod subscription;
         pub mod tpl;
+        pub mod uptime;
         pub mod transport;
         pub mod monitor;
+        pub mod watcher;
+        pub mod webhook;
         pub mod rpc;
 
         pub mod prelude {
@@ -1,5 +1,46 @@
 use crate::imports::*;
 
+/// Changes required to reconcile a set of node
+/// connections with an updated node config.
+#[derive(Debug, Default)]
+pub struct Reconciliation {
+    /// nodes to connect to
+    pub create: Vec<Arc<NodeConfig>>,
+    /// uids of connections to stop and drop
+    pub remove: Vec<u64>,
+    /// nodes whose connection must be restarted due to changed transport settings
+    pub restart: Vec<Arc<NodeConfig>>,
+}
+
+impl Reconciliation {
+    pub fn new(current: &[Arc<NodeConfig>], nodes: &[Arc<NodeConfig>]) -> Self {
+        let current = current
+            .iter()
+            .map(|node| (node.uid(), node))
+            .collect::<AHashMap<_, _>>();
+        let uids = nodes.iter().map(|node| node.uid()).collect::<AHashSet<_>>();
+
+        let mut plan = Self {
+            remove: current
+                .keys()
+                .filter(|uid| !uids.contains(uid))
+                .copied()
+                .collect(),
+            ..Default::default()
+        };
+
+        for node in nodes.iter() {
+            match current.get(&node.uid()) {
+                None => plan.create.push(node.clone()),
+                Some(previous) if previous.is_changed(node) => plan.restart.push(node.clone()),
+                Some(_) => {}
+            }
+        }
+
+        plan
+    }
+}
+
 pub struct Monitor {
     args: Arc<Args>,
     connections: RwLock<AHashMap<u64, Arc<Connection>>>,
@@ -42,15 +83,22 @@ impl Monitor {
         self.connections.read().unwrap().clone()
     }
 
+    pub fn connection(&self, uid: u64) -> Option<Arc<Connection>> {
+        self.connections.read().unwrap().get(&uid).cloned()
+    }
+
     pub fn to_vec(&self) -> Vec<Arc<Connection>> {
         self.connections.read().unwrap().values().cloned().collect()
     }
 
-    /// Process an update to `Server.toml` removing or adding node connections accordingly.
+    /// Process an update to the node config, creating, removing and
+    /// restarting node connections accordingly. Nodes belonging to
+    /// this monitor's service are removed from `global_node_list`.
+    /// Returns the applied [`Reconciliation`].
     pub async fn update_nodes(
         self: &Arc<Self>,
         global_node_list: &mut Vec<Arc<NodeConfig>>,
-    ) -> Result<()> {
+    ) -> Result<Reconciliation> {
         let mut nodes = Vec::new();
         global_node_list.retain(|node| {
             if node.service() == self.service {
@@ -62,40 +110,75 @@ impl Monitor {
         });
 
         let mut connections = self.connections();
+        let current = connections
+            .values()
+            .map(|connection| connection.node().clone())
+            .collect::<Vec<_>>();
+        let plan = Reconciliation::new(&current, &nodes);
 
-        let create: Vec<_> = nodes
+        // connections are dropped even if they fail to stop, so that
+        // `self.connections` reflects the applied plan
+        let mut errors = Vec::new();
+        for uid in plan
+            .remove
             .iter()
-            // .filter(|node| !list.iter().any(|connection| connection.node() == **node))
-            .filter(|node| !connections.contains_key(&node.uid()))
-            .collect();
+            .chain(plan.restart.iter().map(|node| &node.uid))
+        {
+            if let Some(removed) = connections.remove(uid) {
+                if let Err(err) = removed.stop().await {
+                    errors.push((removed.node().clone(), err));
+                }
+            }
+        }
+        for (node, err) in errors {
+            log_error!("Node: {node}: unable to stop connection: {err}");
+        }
 
-        let remove: Vec<_> = connections
-            .iter()
-            .filter_map(|(uid, connection)| {
-                nodes
-                    .iter()
-                    .any(|node| node.uid() == *uid)
-                    .then_some(connection)
-            })
-            .cloned()
-            .collect();
-
-        for node in create {
-            let created = Arc::new(Connection::try_new(
-                &self.args,
-                self.clone(),
-                node.clone(),
-                self.sender.clone(),
-            )?);
-            created.start()?;
-            connections.insert(created.node().uid(), created);
+        // a node whose connection cannot be created (e.g. an unsupported
+        // transport) is skipped so that the rest of the plan is applied
+        for node in plan.create.iter().chain(plan.restart.iter()) {
+            match Connection::try_new(&self.args, self.clone(), node.clone(), self.sender.clone()) {
+                Ok(created) => {
+                    let created = Arc::new(created);
+                    created.start()?;
+                    connections.insert(created.node().uid(), created);
+                }
+                Err(err) => {
+                    log_error!("Node: {node}: {err}");
+                }
+            }
         }
 
-        for removed in remove {
-            removed.stop().await?;
-            connections.remove(&removed.node().uid());
+        self.rebuild_delegates(&connections);
+
+        *self.connections.write().unwrap() = connections;
+
+        Ok(plan)
+    }
+
+    /// Drop delegate bindings referring to connections that are no longer
+    /// present and bind secondary transports of each node to its wRPC
+    /// Borsh connection.
+    fn rebuild_delegates(&self, connections: &AHashMap<u64, Arc<Connection>>) {
+        let is_current = |connection: &Arc<Connection>| {
+            connections
+                .get(&connection.uid())
+                .is_some_and(|current| Arc::ptr_eq(current, connection))
+        };
+
+        self.delegates
+            .write()
+            .unwrap()
+            .retain(|_, delegate| is_current(delegate));
+
+        for connection in connections.values() {
+            if connection
+                .bound_delegate()
+                .is_some_and(|delegate| !is_current(&delegate))
+            {
+                connection.reset_delegate();
+            }
         }
-        // }
 
         let targets = AHashMap::group_from(connections.values().map(|c| {
             (
@@ -114,10 +197,6 @@ impl Monitor {
                 }
             }
         }
-
-        *self.connections.write().unwrap() = connections;
-
-        Ok(())
     }
 
     pub async fn start(self: &Arc<Self>) -> Result<()> {
@@ -172,3 +251,92 @@ impl Monitor {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transport(kind: TransportKind, tls: bool) -> Transport {
+        Transport {
+            kind,
+            tls,
+            template: String::new(),
+        }
+    }
+
+    fn node(address: &str, network: &str, kind: TransportKind, tls: bool) -> Arc<NodeConfig> {
+        NodeConfig::new(
+            &ServiceKind::Kaspa,
+            NetworkId::from_str(network).unwrap(),
+            &transport(kind, tls),
+            "node.example.com",
+            address,
+        )
+    }
+
+    fn borsh(address: &str) -> Arc<NodeConfig> {
+        node(address, "mainnet", TransportKind::WrpcBorsh, true)
+    }
+
+    fn uids(nodes: &[Arc<NodeConfig>]) -> Vec<u64> {
+        nodes.iter().map(|node| node.uid()).collect()
+    }
+
+    #[test]
+    fn test_added_node_is_created() {
+        let a = borsh("wss://a.example.com");
+        let b = borsh("wss://b.example.com");
+        let plan = Reconciliation::new(&[a.clone()], &[a, b.clone()]);
+        assert_eq!(uids(&plan.create), vec![b.uid()]);
+        assert!(plan.remove.is_empty());
+        assert!(plan.restart.is_empty());
+    }
+
+    #[test]
+    fn test_dropped_node_is_removed() {
+        let a = borsh("wss://a.example.com");
+        let b = borsh("wss://b.example.com");
+        let plan = Reconciliation::new(&[a.clone(), b.clone()], &[a]);
+        assert_eq!(plan.remove, vec![b.uid()]);
+        assert!(plan.create.is_empty());
+        assert!(plan.restart.is_empty());
+    }
+
+    #[test]
+    fn test_unchanged_node_is_kept() {
+        let a = borsh("wss://a.example.com");
+        let plan = Reconciliation::new(&[a.clone()], &[borsh("wss://a.example.com")]);
+        assert!(plan.create.is_empty());
+        assert!(plan.remove.is_empty());
+        assert!(plan.restart.is_empty());
+    }
+
+    #[test]
+    fn test_changed_node_is_restarted() {
+        let address = "wss://a.example.com";
+        let current = borsh(address);
+        for changed in [
+            node(address, "mainnet", TransportKind::WrpcJson, true),
+            node(address, "mainnet", TransportKind::WrpcBorsh, false),
+            node(address, "testnet-10", TransportKind::WrpcBorsh, true),
+        ] {
+            let plan = Reconciliation::new(&[current.clone()], &[changed.clone()]);
+            assert_eq!(uids(&plan.restart), vec![changed.uid()]);
+            assert!(plan.create.is_empty());
+            assert!(plan.remove.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_configured_nodes_are_not_removed() {
+        // regression: removal used to select the nodes still present
+        // in the config instead of those dropped from it
+        let a = borsh("wss://a.example.com");
+        let b = borsh("wss://b.example.com");
+        let c = borsh("wss://c.example.com");
+        let plan = Reconciliation::new(&[a.clone(), b.clone(), c.clone()], &[a.clone(), b.clone()]);
+        assert!(!plan.remove.contains(&a.uid()));
+        assert!(!plan.remove.contains(&b.uid()));
+        assert_eq!(plan.remove, vec![c.uid()]);
+    }
+}
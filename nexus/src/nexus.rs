@@ -2,18 +2,69 @@ use crate::imports::*;
 
 struct Inner {
     args: Arc<Args>,
+    // nodes given on the command line (disables the resolver config)
+    nodes: Option<Vec<NodeRpc>>,
+    // currently configured node set
+    config: Mutex<Vec<Arc<NodeConfig>>>,
+    // `enable` settings of nodes overridden at runtime by admin clients
+    overrides: Mutex<AHashMap<u64, bool>>,
+    // serializes application of node set changes
+    reconcile: tokio::sync::Mutex<()>,
+    audit: AuditLog,
+    watcher: ConfigWatcher,
     kaspa: Arc<Monitor>,
     sparkle: Arc<Monitor>,
+    storage: Option<Storage>,
+    uptime: Option<Arc<Uptime>>,
+    alerts: Option<Arc<Alerts>>,
+    analyzer: Option<Arc<Analyzer>>,
+    smtp: Option<SmtpNotifier>,
+    webhook: Option<WebhookNotifier>,
     shutdown_ctl: DuplexChannel<()>,
     events: Channel<Event>,
-    contexts: RwLock<AHashMap<u64, Arc<dyn ContextT>>>,
+    outboxes: RwLock<AHashMap<u64, Arc<Outbox>>>,
+    // notifications dropped by and lagging clients disconnected
+    // from outboxes of clients that are no longer registered
+    dropped: AtomicU64,
+    disconnected: AtomicU64,
+    subscriptions: RwLock<AHashMap<u64, Arc<Subscription>>>,
 }
 
 impl Inner {
-    fn new(args: &Arc<Args>) -> Self {
+    fn new(
+        args: &Arc<Args>,
+        nodes: Option<Vec<NodeRpc>>,
+        storage: Option<Storage>,
+        uptime: Option<Arc<Uptime>>,
+    ) -> Self {
         let events = Channel::unbounded();
+        let watched = if let Some(path) = args.config.as_ref() {
+            vec![path.clone()]
+        } else if nodes.is_some() {
+            vec![]
+        } else {
+            [Some(global_config_path()), local_config_path()]
+                .into_iter()
+                .flatten()
+                .collect()
+        };
+        let alerts = AlertSettings::enable().then(|| Arc::new(Alerts::default()));
+        let smtp = alerts
+            .as_ref()
+            .filter(|_| SmtpSettings::enable())
+            .map(|alerts| SmtpNotifier::new(alerts, SmtpSettings::get().clone()));
+        let webhook = alerts
+            .as_ref()
+            .filter(|_| WebhookSettings::enable())
+            .map(|alerts| WebhookNotifier::new(alerts));
         Self {
             args: args.clone(),
+            nodes,
+            config: Mutex::new(Vec::new()),
+            overrides: Mutex::new(AHashMap::new()),
+            reconcile: tokio::sync::Mutex::new(()),
+            audit: AuditLog::new(),
+            watcher: ConfigWatcher::new(watched),
             kaspa: Arc::new(Monitor::new(
                 args,
                 ServiceKind::Kaspa,
@@ -24,9 +75,18 @@ impl Inner {
                 ServiceKind::Sparkle,
                 events.sender.clone(),
             )),
+            storage,
+            uptime,
+            alerts,
+            analyzer: AnalyzerSettings::enable().then(|| Arc::new(Analyzer::default())),
+            smtp,
+            webhook,
             shutdown_ctl: DuplexChannel::oneshot(),
             events,
-            contexts: RwLock::new(AHashMap::new()),
+            outboxes: RwLock::new(AHashMap::new()),
+            dropped: AtomicU64::new(0),
+            disconnected: AtomicU64::new(0),
+            subscriptions: RwLock::new(AHashMap::new()),
         }
     }
 }
@@ -38,8 +98,25 @@ pub struct Nexus {
 
 impl Nexus {
     pub fn try_new(args: &Arc<Args>) -> Result<Self> {
+        let storage = StorageSettings::enable()
+            .then(Storage::try_new)
+            .transpose()?;
+        let uptime = UptimeSettings::enable()
+            .then(Uptime::try_new)
+            .transpose()?
+            .map(Arc::new);
+
+        let nodes = (!args.node_rpc.is_empty())
+            .then(|| {
+                args.node_rpc
+                    .iter()
+                    .map(|url| NodeRpc::try_parse(url))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?;
+
         Ok(Self {
-            inner: Arc::new(Inner::new(args)),
+            inner: Arc::new(Inner::new(args, nodes, storage, uptime)),
         })
     }
 
@@ -47,7 +124,34 @@ impl Nexus {
         &self.inner.args
     }
 
+    pub fn storage(&self) -> Option<&Storage> {
+        self.inner.storage.as_ref()
+    }
+
+    pub fn uptime(&self) -> Option<&Arc<Uptime>> {
+        self.inner.uptime.as_ref()
+    }
+
+    pub fn alerts(&self) -> Option<&Arc<Alerts>> {
+        self.inner.alerts.as_ref()
+    }
+
+    pub fn analyzer(&self) -> Option<&Arc<Analyzer>> {
+        self.inner.analyzer.as_ref()
+    }
+
     pub async fn start(self: &Arc<Self>) -> Result<()> {
+        if let Some(storage) = self.storage() {
+            storage.start().await?;
+        }
+
+        if let Some(smtp) = self.inner.smtp.as_ref() {
+            smtp.start().await?;
+        }
+
+        if let Some(webhook) = self.inner.webhook.as_ref() {
+            webhook.start().await?;
+        }
         self.inner.kaspa.start().await?;
         self.inner.sparkle.start().await?;
 
@@ -73,6 +177,22 @@ impl Nexus {
             .await
             .expect("Monitor shutdown signal error");
 
+        if let Some(webhook) = self.inner.webhook.as_ref() {
+            webhook.stop().await?;
+        }
+
+        if let Some(smtp) = self.inner.smtp.as_ref() {
+            smtp.stop().await?;
+        }
+
+        if let Some(storage) = self.storage() {
+            storage.stop().await?;
+        }
+
+        if let Some(uptime) = self.uptime() {
+            uptime.save()?;
+        }
+
         Ok(())
     }
 
@@ -82,6 +202,11 @@ impl Nexus {
         let shutdown_ctl_sender = self.inner.shutdown_ctl.response.sender.clone();
 
         let mut update = workflow_core::task::interval(Updates::duration());
+        let mut alerts = workflow_core::task::interval(AlertSettings::poll());
+        let mut analyzer = workflow_core::task::interval(AnalyzerSettings::poll());
+        let mut reload = workflow_core::task::interval(ConfigSettings::poll());
+        let mut uptime = workflow_core::task::interval(UptimeSettings::save());
+        let mut notifications = workflow_core::task::interval(NotificationSettings::max_lag());
 
         loop {
             select! {
@@ -91,32 +216,54 @@ impl Nexus {
                         Ok(event) => {
                             match event {
                                 Event::Start => {
-                                    if let Err(err) = self.update(true).await {
+                                    if let Some(nodes) = self.inner.nodes.as_ref() {
+                                        let result = match try_join_all(nodes.iter().map(NodeRpc::try_into_node)).await {
+                                            Ok(nodes) => self.update_nodes(nodes).await,
+                                            Err(err) => Err(err),
+                                        };
+                                        if let Err(err) = result {
+                                            log_error!("Config [node-rpc]: {err}");
+                                        }
+                                    } else if self.args().config.is_some() {
+                                        if let Err(err) = self.reload().await {
+                                            log_error!("Config [startup]: {err}");
+                                        }
+                                    } else if let Err(err) = self.update(true).await {
                                         log_error!("Config [startup]: {err}");
                                     }
                                 },
                                 Event::Update => {
-                                    if let Err(err) = self.update(false).await {
+                                    let result = if self.inner.nodes.is_some() {
+                                        Ok(())
+                                    } else if self.args().config.is_some() {
+                                        self.reload().await
+                                    } else {
+                                        self.update(false).await
+                                    };
+                                    if let Err(err) = result {
                                         log_error!("Config [update]: {err}");
                                     }
                                 },
                                 Event::Status { status } => {
                                     // println!("Status: {status:?}");
-                                    let update = Update::Status { status };
-                                    for context in self.contexts() {
-                                        // println!("Posting `Status` to context: {}", context.id());
-                                        context.notify(Notification::Update { update : update.clone() }).await.ok();
+                                    if let Some(storage) = self.storage() {
+                                        storage.ingest(&status);
                                     }
+                                    self.broadcast(Update::Status { status }).await;
                                 },
                                 Event::Caps { uid, caps } => {
                                     // println!("Caps: {uid} {caps:?}");
-                                    let update = Update::Caps { uid, caps };
-                                    for context in self.contexts() {
-                                        // println!("Posting `Caps` to context: {}", context.id());
-                                        context.notify(Notification::Update { update : update.clone() }).await.ok();
-                                        // context.notify(update.clone()).await.ok();
+                                    self.broadcast(Update::Caps { uid, caps }).await;
+                                },
+                                Event::State { uid, connected, online, synced, delegate, since } => {
+                                    if let Some(uptime) = self.uptime() {
+                                        let sid = self.connection(uid).map(|connection| connection.sid()).unwrap_or_default();
+                                        uptime.update(uid, sid, connected, online, synced, delegate, since);
                                     }
-
+                                    self.broadcast(Update::State { uid, connected, online, synced, delegate, since }).await;
+                                },
+                                Event::Health { health } => {
+                                    self.broadcast(Update::Health { health }).await;
                                 },
                             }
                         }
@@ -129,7 +276,53 @@ impl Nexus {
                 }
 
                 _ = update.next().fuse() => {
-                    self.inner.events.send(Event::Update).await?;
+                    if self.inner.nodes.is_none() && self.args().config.is_none() {
+                        self.inner.events.send(Event::Update).await?;
+                    }
+                }
+
+                _ = reload.next().fuse() => {
+                    let changed = self.inner.watcher.changed();
+                    if !changed.is_empty() {
+                        for path in changed.iter() {
+                            log_info!("Config: `{}` changed", path.display());
+                        }
+                        if let Err(err) = self.reload().await {
+                            log_error!("Config [reload]: {err} (retaining the current node set)");
+                        }
+                    }
+                }
+
+                _ = alerts.next().fuse() => {
+                    if let Some(alerts) = self.alerts() {
+                        alerts.update(&self.connections()).await;
+                    }
+                }
+
+                _ = analyzer.next().fuse() => {
+                    if let Some(analyzer) = self.analyzer() {
+                        for update in analyzer.analyze(&self.connections()) {
+                            self.broadcast(update).await;
+                        }
+                    }
+                }
+
+                _ = uptime.next().fuse() => {
+                    if let Some(uptime) = self.uptime().cloned() {
+                        match spawn_blocking(move || uptime.save()).await {
+                            Ok(Err(err)) => log_error!("Uptime: unable to save history: {err}"),
+                            Err(err) => log_error!("Uptime: {err}"),
+                            Ok(Ok(())) => {}
+                        }
+                    }
+                }
+
+                _ = notifications.next().fuse() => {
+                    // disconnect clients that stopped reading while no
+                    // notifications are posted to them
+                    for outbox in self.outboxes() {
+                        outbox.expire();
+                    }
                 }
 
                 _ = shutdown_ctl_receiver.recv().fuse() => {
@@ -144,12 +337,60 @@ impl Nexus {
         Ok(())
     }
 
-    async fn update_nodes(
-        self: &Arc<Self>,
-        mut global_node_list: Vec<Arc<NodeConfig>>,
+    /// Apply a new node set. The difference to the current node set
+    /// is logged before it is applied; a node set that fails validation
+    /// or cannot be applied is rejected as a whole, retaining the current
+    /// node set.
+    async fn update_nodes(self: &Arc<Self>, global_node_list: Vec<Arc<NodeConfig>>) -> Result<()> {
+        let guard = self.inner.reconcile.lock().await;
+        let previous = self.inner.config.lock().unwrap().clone();
+        let diff = ConfigDiff::try_new(&previous, &global_node_list)?;
+        if diff.is_empty() {
+            log_info!("Config: no changes");
+            return Ok(());
+        }
+        log_info!("Config: {diff}");
+        if let Err(err) = self.apply_nodes(&guard, &global_node_list).await {
+            if let Err(err) = self.apply_nodes(&guard, &previous).await {
+                log_error!("Config: unable to restore the current node set: {err}");
+            }
+            return Err(err);
+        }
+        *self.inner.config.lock().unwrap() = global_node_list;
+        Ok(())
+    }
+
+    /// Reconcile node connections with the enabled nodes of the node set
+    /// `nodes`, taking `enable` overrides set at runtime into account.
+    /// Callers hold the `reconcile` lock across changes to the node set
+    /// and their application.
+    async fn apply_nodes(
+        &self,
+        _guard: &tokio::sync::MutexGuard<'_, ()>,
+        nodes: &[Arc<NodeConfig>],
     ) -> Result<()> {
-        self.inner.kaspa.update_nodes(&mut global_node_list).await?;
-        self.inner
+        let mut global_node_list = {
+            let overrides = self.inner.overrides.lock().unwrap();
+            nodes
+                .iter()
+                .filter(|node| {
+                    overrides
+                        .get(&node.uid())
+                        .copied()
+                        .unwrap_or(node.is_enabled())
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let sids = self
+            .connections()
+            .iter()
+            .map(|connection| (connection.uid(), connection.sid()))
+            .collect::<AHashMap<_, _>>();
+
+        let kaspa = self.inner.kaspa.update_nodes(&mut global_node_list).await?;
+        let sparkle = self
+            .inner
             .sparkle
             .update_nodes(&mut global_node_list)
             .await?;
@@ -157,9 +398,81 @@ impl Nexus {
         for node in global_node_list.iter() {
             log_error!("Update: Dangling node record: {}", node);
         }
+
+        let now = unixtime_as_millis_f64() as u64;
+        let mut updates = Vec::new();
+        let mut removed = AHashSet::new();
+        // nodes whose connection could not be created are not announced,
+        // restarted nodes without a new connection are removed
+        let mut added = Vec::new();
+        let mut remove = Vec::new();
+        for plan in [kaspa, sparkle] {
+            let restarted = plan
+                .restart
+                .iter()
+                .map(|node| node.uid())
+                .collect::<AHashSet<_>>();
+            remove.extend(plan.remove);
+            for node in plan.create.iter().chain(plan.restart.iter()) {
+                match self.connection(node.uid()) {
+                    Some(connection) => added.push((node.clone(), connection)),
+                    None if restarted.contains(&node.uid()) => remove.push(node.uid()),
+                    None => {}
+                }
+            }
+        }
+
+        // removals precede additions, as a node moved
+        // to another service is removed from one monitor
+        // and added to the other
+        for uid in remove {
+            if let Some(uptime) = self.uptime() {
+                uptime.remove(uid, now);
+            }
+            removed.insert(uid);
+            updates.push(Update::NodeRemoved { uid });
+        }
+        for (node, connection) in added {
+            removed.remove(&node.uid());
+            if let Some(uptime) = self.uptime() {
+                uptime.register(&connection, now);
+            }
+            updates.push(Update::NodeAdded {
+                node: Arc::new(node.info()),
+            });
+        }
+
+        // machines (sid 0 is unknown) left without any node connections
+        let remaining = sids
+            .iter()
+            .filter_map(|(uid, sid)| (!removed.contains(uid)).then_some(*sid))
+            .collect::<AHashSet<_>>();
+        let machines = removed
+            .iter()
+            .filter_map(|uid| sids.get(uid).copied())
+            .filter(|sid| *sid != 0 && !remaining.contains(sid))
+            .collect::<AHashSet<_>>();
+        for sid in machines {
+            updates.push(Update::MachineRemoved { sid });
+        }
+
+        for update in updates {
+            self.broadcast(update).await;
+        }
+
         Ok(())
     }
 
+    /// Reload the config file given with `--config` or,
+    /// if not specified, the global or local config.
+    async fn reload(self: &Arc<Self>) -> Result<()> {
+        let node_list = match self.args().config.as_ref() {
+            Some(path) => load_config_file(path)?,
+            None => load_config()?,
+        };
+        self.update_nodes(node_list).await
+    }
+
     async fn update(self: &Arc<Self>, fallback_to_local: bool) -> Result<()> {
         match update_global_config().await {
             Ok(Some(global_node_list)) => {
@@ -185,43 +498,170 @@ impl Nexus {
         kaspa.into_iter().chain(sparkle).collect::<Vec<_>>()
     }
 
+    /// Select the least loaded available node connection matching
+    /// `target`, skipping nodes flagged by the [`Analyzer`].
+    pub fn resolve(&self, target: &Target) -> Option<Arc<Connection>> {
+        let divergent = self
+            .analyzer()
+            .map(|analyzer| {
+                analyzer
+                    .divergences()
+                    .iter()
+                    .map(|divergence| divergence.uid)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        resolver::resolve(&self.connections(), target, &divergent)
+    }
+
+    /// Current state of all node connections.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            connections: self
+                .connections()
+                .iter()
+                .map(|connection| connection.snapshot())
+                .collect(),
+        }
+    }
+
     pub async fn register_context(&self, context: Arc<dyn ContextT>) {
         println!("Registering context: {}", context.id());
 
-        let connections = self.connections();
-        let caps = connections
-            .iter()
-            .filter_map(|c| {
-                let caps = c.caps()?;
-                let uid = c.uid();
-                Some((uid, caps))
-            })
-            .collect::<Vec<_>>();
+        let outbox = Arc::new(Outbox::new(context.clone()));
+        outbox.start();
 
-        for (uid, caps) in caps {
-            let update = Update::Caps { uid, caps };
-            context
-                .notify(Notification::Update {
-                    update: update.clone(),
-                })
-                .await
-                .ok();
+        outbox.post(Notification::Snapshot {
+            snapshot: Arc::new(self.snapshot()),
+        });
+
+        if let Some(analyzer) = self.analyzer() {
+            for divergence in analyzer.divergences() {
+                outbox.post(Notification::Update {
+                    update: Update::Divergence { divergence },
+                });
+            }
         }
 
-        let mut contexts = self.inner.contexts.write().unwrap();
-        contexts.insert(context.id(), context.clone());
+        let mut outboxes = self.inner.outboxes.write().unwrap();
+        outboxes.insert(context.id(), outbox);
     }
 
     pub async fn unregister_context(&self, id: u64) {
         println!("Unregistering context: {}", id);
 
-        let mut contexts = self.inner.contexts.write().unwrap();
-        contexts.remove(&id);
+        self.inner.subscriptions.write().unwrap().remove(&id);
+        let outbox = self.inner.outboxes.write().unwrap().remove(&id);
+        if let Some(outbox) = outbox {
+            if let Err(err) = outbox.stop().await {
+                log_error!("Notifications: client {id}: {err}");
+            }
+            self.inner
+                .dropped
+                .fetch_add(outbox.dropped(), Ordering::Relaxed);
+            if outbox.is_closed() {
+                self.inner.disconnected.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Notification delivery counters.
+    pub fn notification_stats(&self) -> NotificationStats {
+        let outboxes = self.outboxes();
+        NotificationStats {
+            clients: outboxes.len(),
+            dropped: self.inner.dropped.load(Ordering::Relaxed)
+                + outboxes.iter().map(|outbox| outbox.dropped()).sum::<u64>(),
+            disconnected: self.inner.disconnected.load(Ordering::Relaxed)
+                + outboxes.iter().filter(|outbox| outbox.is_closed()).count() as u64,
+        }
     }
 
-    pub fn contexts(&self) -> Vec<Arc<dyn ContextT>> {
+    pub fn connection(&self, uid: u64) -> Option<Arc<Connection>> {
         self.inner
-            .contexts
+            .kaspa
+            .connection(uid)
+            .or_else(|| self.inner.sparkle.connection(uid))
+    }
+
+    /// Configuration of the connected node with the given uid.
+    pub fn node(&self, uid: u64) -> Option<Arc<NodeConfig>> {
+        self.connection(uid)
+            .map(|connection| connection.node().clone())
+    }
+
+    /// Override the `enable` setting of a configured node
+    /// until the monitor is restarted.
+    async fn set_node_enabled(&self, uid: u64, enable: bool) -> Result<()> {
+        let guard = self.inner.reconcile.lock().await;
+        let Some(node) = self
+            .inner
+            .config
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|node| node.uid() == uid)
+            .cloned()
+        else {
+            return Err(Error::NodeNotFound(uid));
+        };
+
+        let changed = {
+            let mut overrides = self.inner.overrides.lock().unwrap();
+            let previous = overrides.get(&uid).copied().unwrap_or(node.is_enabled());
+            overrides.insert(uid, enable);
+            previous != enable
+        };
+
+        if changed {
+            let config = self.inner.config.lock().unwrap().clone();
+            self.apply_nodes(&guard, &config).await?;
+        }
+        Ok(())
+    }
+
+    /// Perform an admin operation on behalf of the client `ctx`,
+    /// recording the operation and its result in the audit log.
+    async fn admin<F>(&self, ctx: &dyn ContextT, action: String, op: F) -> Result<()>
+    where
+        F: Future<Output = Result<()>>,
+    {
+        let result = if ctx.role() == Role::Admin {
+            op.await
+        } else {
+            Err(Error::Unauthorized(Role::Admin))
+        };
+        self.inner
+            .audit
+            .record(ctx, &action, result.as_ref().map(|_| ()));
+        result
+    }
+
+    /// Queue an update for delivery to all registered contexts,
+    /// skipping contexts whose [`Subscription`] does not accept it.
+    pub async fn broadcast(&self, update: Update) {
+        let node = update.uid().and_then(|uid| self.node(uid));
+        for outbox in self.outboxes() {
+            if let Some(subscription) = self.subscription(outbox.context().id()) {
+                if !subscription.accepts(&update, node.as_deref()) {
+                    continue;
+                }
+            }
+
+            outbox.post(Notification::Update {
+                update: update.clone(),
+            });
+        }
+    }
+
+    pub fn subscription(&self, id: u64) -> Option<Arc<Subscription>> {
+        self.inner.subscriptions.read().unwrap().get(&id).cloned()
+    }
+
+    pub fn outboxes(&self) -> Vec<Arc<Outbox>> {
+        self.inner
+            .outboxes
             .read()
             .unwrap()
             .values()
@@ -257,6 +697,177 @@ impl Nexus {
         };
         Ok(response)
     }
+
+    pub async fn get_status_history_call(
+        &self,
+        _ctx: &dyn ContextT,
+        request: GetStatusHistoryRequest,
+    ) -> Result<GetStatusHistoryResponse> {
+        let GetStatusHistoryRequest {
+            uid,
+            from,
+            to,
+            metrics,
+            resolution,
+        } = request;
+
+        let storage = self.storage().ok_or(Error::StorageDisabled)?;
+        let samples = storage.history(uid, from, to, &metrics, resolution).await?;
+
+        Ok(GetStatusHistoryResponse {
+            uid,
+            metrics,
+            samples,
+        })
+    }
+
+    pub async fn get_node_timeline_call(
+        &self,
+        _ctx: &dyn ContextT,
+        request: GetNodeTimelineRequest,
+    ) -> Result<GetNodeTimelineResponse> {
+        let GetNodeTimelineRequest { uid } = request;
+
+        let storage = self.storage().ok_or(Error::StorageDisabled)?;
+        let segments = storage.timeline(uid).await?;
+
+        Ok(GetNodeTimelineResponse { uid, segments })
+    }
+
+    pub async fn get_snapshot_call(
+        &self,
+        _ctx: &dyn ContextT,
+        _request: GetSnapshotRequest,
+    ) -> Result<GetSnapshotResponse> {
+        Ok(GetSnapshotResponse {
+            snapshot: Arc::new(self.snapshot()),
+        })
+    }
+
+    pub async fn subscribe_call(
+        &self,
+        ctx: &dyn ContextT,
+        request: SubscribeRequest,
+    ) -> Result<SubscribeResponse> {
+        self.inner
+            .subscriptions
+            .write()
+            .unwrap()
+            .insert(ctx.id(), Arc::new(Subscription::from(request)));
+
+        Ok(SubscribeResponse {})
+    }
+
+    pub async fn resolve_call(
+        &self,
+        _ctx: &dyn ContextT,
+        request: ResolveRequest,
+    ) -> Result<ResolveResponse> {
+        let ResolveRequest {
+            service,
+            network_id,
+            tls,
+            encoding,
+        } = request;
+
+        let target = Target::try_new(&service, network_id, &tls, &encoding)?;
+        let connection = self.resolve(&target).ok_or(Error::NoNodesAvailable)?;
+
+        Ok(ResolveResponse {
+            uid: connection.uid(),
+            url: connection.address().to_string(),
+        })
+    }
+
+    pub async fn reconnect_node_call(
+        &self,
+        ctx: &dyn ContextT,
+        request: ReconnectNodeRequest,
+    ) -> Result<ReconnectNodeResponse> {
+        let ReconnectNodeRequest { uid } = request;
+
+        self.admin(ctx, format!("reconnect-node {uid:016x}"), async {
+            let connection = self.connection(uid).ok_or(Error::NodeNotFound(uid))?;
+            connection.reconnect().await
+        })
+        .await?;
+
+        Ok(ReconnectNodeResponse {})
+    }
+
+    pub async fn disable_node_call(
+        &self,
+        ctx: &dyn ContextT,
+        request: DisableNodeRequest,
+    ) -> Result<DisableNodeResponse> {
+        let DisableNodeRequest { uid } = request;
+
+        self.admin(
+            ctx,
+            format!("disable-node {uid:016x}"),
+            self.set_node_enabled(uid, false),
+        )
+        .await?;
+
+        Ok(DisableNodeResponse {})
+    }
+
+    pub async fn enable_node_call(
+        &self,
+        ctx: &dyn ContextT,
+        request: EnableNodeRequest,
+    ) -> Result<EnableNodeResponse> {
+        let EnableNodeRequest { uid } = request;
+
+        self.admin(
+            ctx,
+            format!("enable-node {uid:016x}"),
+            self.set_node_enabled(uid, true),
+        )
+        .await?;
+
+        Ok(EnableNodeResponse {})
+    }
+
+    pub async fn force_config_update_call(
+        &self,
+        ctx: &dyn ContextT,
+        _request: ForceConfigUpdateRequest,
+    ) -> Result<ForceConfigUpdateResponse> {
+        self.admin(ctx, "force-config-update".to_string(), async {
+            Ok(self.inner.events.send(Event::Update).await?)
+        })
+        .await?;
+
+        Ok(ForceConfigUpdateResponse {})
+    }
+
+    pub async fn trigger_abort_call(
+        &self,
+        ctx: &dyn ContextT,
+        request: TriggerAbortRequest,
+    ) -> Result<TriggerAbortResponse> {
+        let TriggerAbortRequest { uid } = request;
+
+        self.admin(ctx, format!("trigger-abort {uid:016x}"), async {
+            let connection = self.connection(uid).ok_or(Error::NodeNotFound(uid))?;
+            connection.trigger_abort()
+        })
+        .await?;
+
+        Ok(TriggerAbortResponse {})
+    }
+
+    pub async fn get_uptime_call(
+        &self,
+        _ctx: &dyn ContextT,
+        _request: GetUptimeRequest,
+    ) -> Result<GetUptimeResponse> {
+        let uptime = self.uptime().ok_or(Error::UptimeDisabled)?;
+        Ok(GetUptimeResponse {
+            report: Arc::new(uptime.report()),
+        })
+    }
 }
 
 #[async_trait]
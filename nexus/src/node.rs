@@ -1,5 +1,162 @@
 use crate::imports::*;
 
+// default node RPC ports for each network type (kaspad selects its ports by
+// network type, regardless of the testnet suffix): (network, grpc, wrpc-borsh, wrpc-json)
+const DEFAULT_RPC_PORTS: &[(NetworkType, u16, u16, u16)] = &[
+    (NetworkType::Mainnet, 16110, 17110, 18110),
+    (NetworkType::Testnet, 16210, 17210, 18210),
+    (NetworkType::Simnet, 16510, 17510, 18510),
+    (NetworkType::Devnet, 16610, 17610, 18610),
+];
+// time allowed for querying the network of a node given by its RPC URL
+const NETWORK_QUERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Kaspa node RPC endpoint given on the command line as `ws://`, `wss://`
+/// or `grpc://` URL, optionally followed by the network id as URL fragment
+/// (`ws://127.0.0.1:9000#testnet-11`). The transport is inferred from the
+/// URL scheme; wRPC endpoints use the Borsh encoding unless the port is
+/// a default wRPC JSON port.
+#[derive(Clone, Debug)]
+pub struct NodeRpc {
+    url: String,
+    host: String,
+    port: Option<u16>,
+    kind: TransportKind,
+    tls: bool,
+    network: Option<NetworkId>,
+}
+
+impl NodeRpc {
+    pub fn try_parse(spec: &str) -> Result<Self> {
+        let (url, network) = match spec.split_once('#') {
+            Some((url, network)) => (
+                url,
+                Some(NetworkId::from_str(network).map_err(|err| {
+                    Error::config(format!("Invalid network `{network}` of `{spec}`: {err}"))
+                })?),
+            ),
+            None => (spec, None),
+        };
+
+        let (scheme, rest) = url
+            .split_once("://")
+            .ok_or_else(|| Error::config(format!("Node RPC URL is missing a scheme: `{url}`")))?;
+        let authority = rest.split('/').next().unwrap_or_default();
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !port.ends_with(']') => (host, Some(port)),
+            _ => (authority, None),
+        };
+        let port = port
+            .map(|port| {
+                port.parse::<u16>()
+                    .map_err(|_| Error::config(format!("Invalid port `{port}` of `{url}`")))
+            })
+            .transpose()?;
+
+        let json = port.is_some_and(|port| {
+            DEFAULT_RPC_PORTS
+                .iter()
+                .any(|(_, _, _, json)| *json == port)
+        });
+        let (kind, tls) = match scheme {
+            "ws" | "wss" if json => (TransportKind::WrpcJson, scheme == "wss"),
+            "ws" | "wss" => (TransportKind::WrpcBorsh, scheme == "wss"),
+            "grpc" => (TransportKind::Grpc, false),
+            _ => {
+                return Err(Error::config(format!(
+                    "Unsupported node RPC URL scheme `{scheme}`: `{url}`"
+                )))
+            }
+        };
+
+        Ok(Self {
+            url: url.to_string(),
+            host: host
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .to_string(),
+            port,
+            kind,
+            tls,
+            network,
+        })
+    }
+
+    /// Network of the node inferred from its default RPC port.
+    fn network_from_port(&self) -> Result<NetworkId> {
+        let port = self.port.ok_or_else(|| {
+            Error::config(format!(
+                "Unable to infer the network of `{}`, specify it as `#<network>`",
+                self.url
+            ))
+        })?;
+        let (network_type, ..) = DEFAULT_RPC_PORTS
+            .iter()
+            .find(|(_, grpc, borsh, json)| [*grpc, *borsh, *json].contains(&port))
+            .ok_or_else(|| {
+                Error::config(format!(
+                    "Unable to infer the network from port {port} of `{}`, specify it as `#<network>`",
+                    self.url
+                ))
+            })?;
+        match network_type {
+            NetworkType::Testnet => Err(Error::config(format!(
+                "Unable to infer the testnet suffix of `{}`, specify it as `#testnet-<suffix>`",
+                self.url
+            ))),
+            network_type => Ok(NetworkId::new(*network_type)),
+        }
+    }
+
+    /// Create the node config, using the network given with the URL or,
+    /// if not specified, the network reported by the node. The default RPC
+    /// ports are used to infer the network only if the node can not be
+    /// queried.
+    pub async fn try_into_node(&self) -> Result<Arc<NodeConfig>> {
+        let network = match self.network {
+            Some(network) => network,
+            None => {
+                let query = async {
+                    match self.kind {
+                        TransportKind::WrpcBorsh => {
+                            rpc::kaspa::get_network(WrpcEncoding::Borsh, &self.url).await
+                        }
+                        TransportKind::WrpcJson => {
+                            rpc::kaspa::get_network(WrpcEncoding::SerdeJson, &self.url).await
+                        }
+                        TransportKind::Grpc => rpc::grpc::get_network(&self.url).await,
+                    }
+                };
+                match tokio::time::timeout(NETWORK_QUERY_TIMEOUT, query).await {
+                    Ok(Ok(network)) => network,
+                    Ok(Err(err)) => {
+                        log_warn!("Node: unable to query the network of `{}`: {err}", self.url);
+                        self.network_from_port()?
+                    }
+                    Err(_) => {
+                        log_warn!("Node: timeout querying the network of `{}`", self.url);
+                        self.network_from_port()?
+                    }
+                }
+            }
+        };
+
+        let transport = Transport {
+            kind: self.kind,
+            tls: self.tls,
+            template: String::new(),
+        };
+
+        Ok(NodeConfig::new(
+            &ServiceKind::Kaspa,
+            network,
+            &transport,
+            &self.host,
+            &self.url,
+        ))
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NodeConfig {
     // uid of the node connection (hash(address))
@@ -14,13 +171,20 @@ pub struct NodeConfig {
     // protocol+encoding
     #[serde(rename = "transport-type")]
     pub transport_kind: TransportKind,
+    // transport uses TLS
+    #[serde(skip)]
+    pub tls: bool,
     // node network id
     pub network: NetworkId,
     // entry is enabled
     pub enable: Option<bool>,
+    // resolver load multiplier (a node with bias 2.0 is selected as if
+    // serving twice as many clients)
+    pub bias: Option<f64>,
     // domain name (abc.example.com)
     pub fqdn: String,
-    // contains hash(fqdn+network_id)
+    // contains hash(fqdn+network_id+tls)
+    #[serde(skip)]
     pub network_node_uid: u64,
     // pub params: PathParams,
 }
@@ -68,14 +232,50 @@ impl NodeConfig {
             fqdn,
             address,
             transport_kind: *kind,
+            tls: *tls,
             network,
             enable: None,
+            bias: None,
             network_node_uid,
         };
 
         Arc::new(node)
     }
 
+    pub fn is_enabled(&self) -> bool {
+        self.enable.unwrap_or(true)
+    }
+
+    /// Whether `next`, a config of the same node (uid), differs in any
+    /// setting that requires its connection to be re-established.
+    pub fn is_changed(&self, next: &NodeConfig) -> bool {
+        self.service != next.service
+            || self.transport_kind != next.transport_kind
+            || self.tls != next.tls
+            || self.bias() != next.bias()
+            || self.network != next.network
+            || self.fqdn != next.fqdn
+            || self.network_node_uid != next.network_node_uid
+    }
+
+    /// Node config as presented to monitor clients.
+    pub fn info(&self) -> NodeInfo {
+        NodeInfo {
+            uid: self.uid,
+            service: self.service.to_string(),
+            transport: self.transport_kind.to_string(),
+            tls: self.tls,
+            network_id: self.network,
+            fqdn: self.fqdn.clone(),
+            address: self.address.clone(),
+        }
+    }
+
+    /// Resolver bias scaled by [`BIAS_SCALE`] (at least 1).
+    pub fn bias(&self) -> u64 {
+        ((self.bias.unwrap_or(1.0).max(0.0) * BIAS_SCALE as f64) as u64).max(1)
+    }
+
     #[inline]
     pub fn service(&self) -> ServiceKind {
         self.service
@@ -91,6 +291,11 @@ impl NodeConfig {
         self.transport_kind
     }
 
+    #[inline]
+    pub fn tls(&self) -> bool {
+        self.tls
+    }
+
     #[inline]
     pub fn network_node_uid(&self) -> u64 {
         self.network_node_uid
@@ -0,0 +1,224 @@
+use crate::imports::*;
+
+/// Notification delivery counters reported by [`Nexus::notification_stats`].
+#[derive(Clone, Debug, Default)]
+pub struct NotificationStats {
+    // number of registered clients
+    pub clients: usize,
+    pub dropped: u64,
+    // clients disconnected for lagging behind
+    pub disconnected: u64,
+}
+
+enum Entry {
+    // the latest status of the node is held in `Queue::statuses`
+    Status(u64),
+    Notification(Notification),
+}
+
+#[derive(Default)]
+struct Queue {
+    entries: VecDeque<(Instant, Entry)>,
+    statuses: AHashMap<u64, Arc<Status>>,
+    // the consumer was disconnected, further notifications are discarded
+    closed: bool,
+}
+
+/// Outbound notification queue of a client context. Notifications are
+/// delivered by a dedicated task, so a slow consumer does not delay the
+/// [`Nexus`] event loop or other consumers.
+///
+/// A pending `Status` update of a node is replaced by a newer status of
+/// the same node. Other notifications are dropped while the queue holds
+/// [`NotificationSettings::queue_size`] entries. A consumer is disconnected
+/// once its oldest pending notification is older than
+/// [`NotificationSettings::max_lag`]. The lag is checked when notifications
+/// are posted or taken for delivery and by [`Outbox::expire`], which the
+/// [`Nexus`] calls periodically so that idle consumers that stopped reading
+/// are disconnected as well.
+pub struct Outbox {
+    context: Arc<dyn ContextT>,
+    queue: Mutex<Queue>,
+    signal: Channel<()>,
+    dropped: AtomicU64,
+    shutdown_ctl: DuplexChannel<()>,
+}
+
+impl Outbox {
+    pub fn new(context: Arc<dyn ContextT>) -> Self {
+        Self {
+            context,
+            queue: Default::default(),
+            signal: Channel::bounded(1),
+            dropped: AtomicU64::new(0),
+            shutdown_ctl: DuplexChannel::oneshot(),
+        }
+    }
+
+    pub fn context(&self) -> &Arc<dyn ContextT> {
+        &self.context
+    }
+
+    /// Number of notifications dropped, replaced by a newer
+    /// status or failed to be delivered.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Consumer was disconnected for lagging behind.
+    pub fn is_closed(&self) -> bool {
+        self.queue.lock().unwrap().closed
+    }
+
+    /// Disconnect the consumer if its oldest pending notification is
+    /// older than [`NotificationSettings::max_lag`], discarding the queue.
+    /// Returns `true` if the consumer is disconnected.
+    pub fn expire(&self) -> bool {
+        let mut queue = self.queue.lock().unwrap();
+        if queue.closed {
+            return true;
+        }
+        let Some(pending) = Self::close_lagging(&mut queue, Instant::now()) else {
+            return false;
+        };
+        drop(queue);
+
+        self.disconnect(pending, pending);
+        true
+    }
+
+    /// Close the queue if its oldest pending notification is older than
+    /// [`NotificationSettings::max_lag`], returning the number of
+    /// discarded notifications.
+    fn close_lagging(queue: &mut Queue, now: Instant) -> Option<u64> {
+        let (queued, _) = queue.entries.front()?;
+        if now.duration_since(*queued) <= NotificationSettings::max_lag() {
+            return None;
+        }
+
+        let pending = queue.entries.len() as u64;
+        queue.closed = true;
+        // release the memory held by the queue of the lagging consumer
+        queue.entries = VecDeque::new();
+        queue.statuses = AHashMap::new();
+        Some(pending)
+    }
+
+    fn disconnect(&self, pending: u64, dropped: u64) {
+        self.dropped.fetch_add(dropped, Ordering::Relaxed);
+        log_warn!(
+            "Notifications: disconnecting lagging client {} ({pending} notifications pending)",
+            self.context.id()
+        );
+        if let Err(err) = self.context.disconnect() {
+            log_error!("Notifications: client {}: {err}", self.context.id());
+        }
+    }
+
+    /// Queue a notification for delivery.
+    pub fn post(&self, notification: Notification) {
+        let mut queue = self.queue.lock().unwrap();
+        if queue.closed {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        let now = Instant::now();
+        if let Some(pending) = Self::close_lagging(&mut queue, now) {
+            drop(queue);
+            self.disconnect(pending, pending + 1);
+            return;
+        }
+
+        let full = queue.entries.len() >= NotificationSettings::queue_size();
+        match notification {
+            Notification::Update {
+                update: Update::Status { status },
+            } => {
+                let uid = status.uid();
+                if queue.statuses.contains_key(&uid) {
+                    queue.statuses.insert(uid, status);
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                } else if full {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                } else {
+                    queue.statuses.insert(uid, status);
+                    queue.entries.push_back((now, Entry::Status(uid)));
+                }
+            }
+            notification => {
+                if full {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                } else {
+                    queue
+                        .entries
+                        .push_back((now, Entry::Notification(notification)));
+                }
+            }
+        }
+
+        self.signal.try_send(()).ok();
+    }
+
+    fn take(&self) -> Option<Notification> {
+        let mut queue = self.queue.lock().unwrap();
+        if let Some(pending) = Self::close_lagging(&mut queue, Instant::now()) {
+            drop(queue);
+            self.disconnect(pending, pending);
+            return None;
+        }
+        let (_, entry) = queue.entries.pop_front()?;
+        match entry {
+            Entry::Status(uid) => {
+                let status = queue.statuses.remove(&uid)?;
+                Some(Notification::Update {
+                    update: Update::Status { status },
+                })
+            }
+            Entry::Notification(notification) => Some(notification),
+        }
+    }
+
+    pub fn start(self: &Arc<Self>) {
+        let this = self.clone();
+        tokio::spawn(async move {
+            if let Err(error) = this.task().await {
+                log_error!("Outbox task error: {:?}", error);
+            }
+        });
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        self.shutdown_ctl
+            .signal(())
+            .await
+            .expect("Outbox shutdown signal error");
+        Ok(())
+    }
+
+    async fn task(self: Arc<Self>) -> Result<()> {
+        let signal = self.signal.receiver.clone();
+        let shutdown_ctl_receiver = self.shutdown_ctl.request.receiver.clone();
+        let shutdown_ctl_sender = self.shutdown_ctl.response.sender.clone();
+
+        loop {
+            select! {
+                _ = signal.recv().fuse() => {
+                    while let Some(notification) = self.take() {
+                        if self.context.notify(notification).await.is_err() {
+                            self.dropped.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                }
+
+                _ = shutdown_ctl_receiver.recv().fuse() => {
+                    break;
+                },
+            }
+        }
+
+        shutdown_ctl_sender.send(()).await.unwrap();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+use crate::imports::*;
+
+/// Node connection requested from the resolver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Target {
+    pub service: ServiceKind,
+    pub network_id: NetworkId,
+    pub tls: TlsKind,
+    pub transport: TransportKind,
+}
+
+impl Target {
+    /// Create a target from the `{service}/{network}/{tls}/{encoding}`
+    /// resolver path segments, where `tls` is one of `tls`, `none` or `any`
+    /// and `encoding` is `borsh` or `json` (wRPC) or `protobuf` (gRPC).
+    pub fn try_new(
+        service: &str,
+        network_id: NetworkId,
+        tls: &str,
+        encoding: &str,
+    ) -> Result<Self> {
+        let service = match service {
+            "kaspa" => ServiceKind::Kaspa,
+            "sparkle" => ServiceKind::Sparkle,
+            _ => return Err(Error::custom(format!("Unknown service: `{service}`"))),
+        };
+        let tls = match tls {
+            "tls" => TlsKind::Tls,
+            "none" => TlsKind::None,
+            "any" => TlsKind::Any,
+            _ => return Err(Error::custom(format!("Unknown TLS mode: `{tls}`"))),
+        };
+        let transport = match encoding {
+            "borsh" => TransportKind::WrpcBorsh,
+            "json" => TransportKind::WrpcJson,
+            "protobuf" => TransportKind::Grpc,
+            _ => return Err(Error::custom(format!("Unknown encoding: `{encoding}`"))),
+        };
+
+        Ok(Self {
+            service,
+            network_id,
+            tls,
+            transport,
+        })
+    }
+
+    /// Create a target from resolver path segments, parsing the network id.
+    pub fn try_parse(service: &str, network: &str, tls: &str, encoding: &str) -> Result<Self> {
+        let network_id = NetworkId::from_str(network)
+            .map_err(|_| Error::custom(format!("Unknown network: `{network}`")))?;
+        Self::try_new(service, network_id, tls, encoding)
+    }
+
+    pub fn matches(&self, node: &NodeConfig) -> bool {
+        node.service() == self.service
+            && node.network == self.network_id
+            && node.transport_kind() == self.transport
+            && match self.tls {
+                TlsKind::Tls => node.tls(),
+                TlsKind::None => !node.tls(),
+                TlsKind::Any => true,
+            }
+    }
+}
+
+/// Select the least loaded connection matching `target`, weighting the
+/// load by the node bias (see [`Connection::score`]). Connections are
+/// skipped unless their node is available (online and under its client
+/// and file descriptor limits) and synced. Nodes listed in `divergent`
+/// (uids of nodes lagging behind or diverging from the network) are
+/// skipped as well. Equally loaded nodes are selected at random.
+pub fn resolve(
+    connections: &[Arc<Connection>],
+    target: &Target,
+    divergent: &AHashSet<u64>,
+) -> Option<Arc<Connection>> {
+    let candidates = connections
+        .iter()
+        .filter(|connection| connection.connected() && target.matches(connection.node()))
+        .filter(|connection| {
+            let delegate = connection.delegate();
+            delegate.is_available()
+                && delegate.is_synced()
+                && !divergent.contains(&connection.uid())
+                && !divergent.contains(&delegate.uid())
+        })
+        .map(|connection| (connection.score(), connection))
+        .collect::<Vec<_>>();
+
+    let min = candidates.iter().map(|(score, _)| *score).min()?;
+    let best = candidates
+        .into_iter()
+        .filter_map(|(score, connection)| (score == min).then_some(connection))
+        .collect::<Vec<_>>();
+
+    let index = rand::thread_rng().gen_range(0..best.len());
+    Some(best[index].clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENTS_LIMIT: u64 = 100;
+
+    fn mainnet() -> NetworkId {
+        NetworkId::from_str("mainnet").unwrap()
+    }
+
+    fn target() -> Target {
+        Target::try_parse("kaspa", "mainnet", "tls", "borsh").unwrap()
+    }
+
+    fn connection(address: &str, bias: Option<f64>) -> Arc<Connection> {
+        let args = Arc::new(Args {
+            trace: false,
+            debug: false,
+            verbose: false,
+            node_rpc: Vec::new(),
+            config: None,
+        });
+        let events = Channel::unbounded();
+        let monitor = Arc::new(Monitor::new(
+            &args,
+            ServiceKind::Kaspa,
+            events.sender.clone(),
+        ));
+        let transport = Transport {
+            kind: TransportKind::WrpcBorsh,
+            tls: true,
+            template: String::new(),
+        };
+        let mut node = NodeConfig::new(
+            &ServiceKind::Kaspa,
+            mainnet(),
+            &transport,
+            "node.example.com",
+            address,
+        );
+        Arc::make_mut(&mut node).bias = bias;
+        let connection = Connection::try_new(&args, monitor, node, events.sender).unwrap();
+        connection.set_state(true, true, 0, CLIENTS_LIMIT);
+        Arc::new(connection)
+    }
+
+    #[test]
+    fn test_target_is_parsed_from_path_segments() {
+        let target = Target::try_new("sparkle", mainnet(), "any", "json").unwrap();
+        assert_eq!(target.service, ServiceKind::Sparkle);
+        assert_eq!(target.network_id, mainnet());
+        assert_eq!(target.tls, TlsKind::Any);
+        assert_eq!(target.transport, TransportKind::WrpcJson);
+
+        let target = Target::try_new("kaspa", mainnet(), "none", "protobuf").unwrap();
+        assert_eq!(target.service, ServiceKind::Kaspa);
+        assert_eq!(target.tls, TlsKind::None);
+        assert_eq!(target.transport, TransportKind::Grpc);
+
+        let target = target();
+        assert_eq!(target.tls, TlsKind::Tls);
+        assert_eq!(target.transport, TransportKind::WrpcBorsh);
+    }
+
+    #[test]
+    fn test_unknown_path_segments_are_rejected() {
+        assert!(Target::try_new("bitcoin", mainnet(), "tls", "borsh").is_err());
+        assert!(Target::try_new("kaspa", mainnet(), "ssl", "borsh").is_err());
+        assert!(Target::try_new("kaspa", mainnet(), "tls", "xml").is_err());
+        assert!(Target::try_parse("kaspa", "moonnet", "tls", "borsh").is_err());
+    }
+
+    #[test]
+    fn test_least_loaded_node_is_selected() {
+        let a = connection("wss://a.example.com", None);
+        let b = connection("wss://b.example.com", None);
+        a.set_state(true, true, 10, CLIENTS_LIMIT);
+        b.set_state(true, true, 5, CLIENTS_LIMIT);
+
+        let selected = resolve(&[a, b.clone()], &target(), &AHashSet::new()).unwrap();
+        assert_eq!(selected.uid(), b.uid());
+    }
+
+    #[test]
+    fn test_bias_weights_node_load() {
+        let a = connection("wss://a.example.com", Some(4.0));
+        let b = connection("wss://b.example.com", None);
+        a.set_state(true, true, 1, CLIENTS_LIMIT);
+        b.set_state(true, true, 5, CLIENTS_LIMIT);
+
+        let selected = resolve(&[a, b.clone()], &target(), &AHashSet::new()).unwrap();
+        assert_eq!(selected.uid(), b.uid());
+    }
+
+    #[test]
+    fn test_divergent_node_is_skipped() {
+        let a = connection("wss://a.example.com", None);
+        let b = connection("wss://b.example.com", None);
+        b.set_state(true, true, 10, CLIENTS_LIMIT);
+
+        let divergent = AHashSet::from([a.uid()]);
+        let selected = resolve(&[a.clone(), b.clone()], &target(), &divergent).unwrap();
+        assert_eq!(selected.uid(), b.uid());
+
+        let divergent = AHashSet::from([a.uid(), b.uid()]);
+        assert!(resolve(&[a, b], &target(), &divergent).is_none());
+    }
+
+    #[test]
+    fn test_unavailable_nodes_are_skipped() {
+        let offline = connection("wss://a.example.com", None);
+        let unsynced = connection("wss://b.example.com", None);
+        let saturated = connection("wss://c.example.com", None);
+        let available = connection("wss://d.example.com", None);
+        offline.set_state(false, true, 0, CLIENTS_LIMIT);
+        unsynced.set_state(true, false, 0, CLIENTS_LIMIT);
+        saturated.set_state(true, true, CLIENTS_LIMIT, CLIENTS_LIMIT);
+        available.set_state(true, true, 50, CLIENTS_LIMIT);
+
+        let connections = [offline, unsynced, saturated, available.clone()];
+        let selected = resolve(&connections, &target(), &AHashSet::new()).unwrap();
+        assert_eq!(selected.uid(), available.uid());
+
+        assert!(resolve(&connections[..3], &target(), &AHashSet::new()).is_none());
+    }
+
+    #[test]
+    fn test_non_matching_nodes_are_skipped() {
+        let a = connection("wss://a.example.com", None);
+        let target = Target::try_parse("kaspa", "testnet-11", "tls", "borsh").unwrap();
+        assert!(resolve(&[a.clone()], &target, &AHashSet::new()).is_none());
+        let target = Target::try_parse("kaspa", "mainnet", "none", "borsh").unwrap();
+        assert!(resolve(&[a], &target, &AHashSet::new()).is_none());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::imports::*;
+pub use kaspa_grpc_client::GrpcClient;
+pub use kaspa_rpc_core::api::rpc::RpcApi;
+
+// interval at which the gRPC connection state is
+// checked and (re)connection attempts are made
+const CONNECTION_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+struct Inner {
+    client: ArcSwapOption<GrpcClient>,
+    url: String,
+    ctl_multiplexer: Multiplexer<Ctl>,
+    is_running: AtomicBool,
+    shutdown_ctl: DuplexChannel<()>,
+    last_metrics_data: Mutex<Option<MetricsData>>,
+}
+
+/// gRPC client wrapper. Unlike the wRPC client, [`GrpcClient`] does not
+/// provide a connection control channel, so this wrapper runs a background
+/// task that establishes the connection, monitors it and relays connection
+/// state changes as [`Ctl`] messages via its own [`Multiplexer`].
+#[derive(Clone)]
+pub struct Client {
+    inner: Arc<Inner>,
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GrpcClient")
+            .field("url", &self.inner.url)
+            .field("connected", &self.is_connected())
+            .finish()
+    }
+}
+
+impl Client {
+    pub fn try_new(url: &str) -> Result<Self> {
+        if !url.starts_with("grpc://") {
+            return Err(Error::config(format!(
+                "gRPC address must use the `grpc://` scheme: {url}"
+            )));
+        }
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                client: ArcSwapOption::new(None),
+                url: url.to_string(),
+                ctl_multiplexer: Multiplexer::new(),
+                is_running: AtomicBool::new(false),
+                shutdown_ctl: DuplexChannel::oneshot(),
+                last_metrics_data: Mutex::new(None),
+            }),
+        })
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.inner
+            .client
+            .load()
+            .as_ref()
+            .is_some_and(|client| client.is_connected())
+    }
+
+    fn client(&self) -> Result<Arc<GrpcClient>> {
+        self.inner.client.load_full().ok_or(Error::NotConnected)
+    }
+
+    async fn task(self) -> Result<()> {
+        let shutdown_ctl_receiver = self.inner.shutdown_ctl.request.receiver.clone();
+        let shutdown_ctl_sender = self.inner.shutdown_ctl.response.sender.clone();
+        let mut interval = interval(CONNECTION_CHECK_INTERVAL);
+
+        loop {
+            select! {
+                _ = interval.next().fuse() => {
+                    if self.is_connected() {
+                        continue;
+                    }
+
+                    // drop a stale client and notify of disconnection
+                    if let Some(client) = self.inner.client.swap(None) {
+                        client.disconnect().await.ok();
+                        self.inner.ctl_multiplexer.broadcast(Ctl::Disconnect).await.ok();
+                    }
+
+                    if let Ok(client) = GrpcClient::connect(self.inner.url.clone()).await {
+                        self.inner.client.store(Some(Arc::new(client)));
+                        self.inner.ctl_multiplexer.broadcast(Ctl::Connect).await.ok();
+                    }
+                }
+
+                _ = shutdown_ctl_receiver.recv().fuse() => {
+                    break;
+                },
+            }
+        }
+
+        if let Some(client) = self.inner.client.swap(None) {
+            client.disconnect().await.ok();
+            self.inner
+                .ctl_multiplexer
+                .broadcast(Ctl::Disconnect)
+                .await
+                .ok();
+        }
+
+        shutdown_ctl_sender.send(()).await.unwrap();
+
+        Ok(())
+    }
+}
+
+/// Query the network of the node at `url` over a short-lived connection.
+pub async fn get_network(url: &str) -> Result<NetworkId> {
+    let client = GrpcClient::connect(url.to_string()).await?;
+    let network = rpc::kaspa::get_network_id(&client).await;
+    client.disconnect().await.ok();
+    network
+}
+
+impl rpc::ClientT for Client {
+    fn multiplexer(&self) -> Multiplexer<Ctl> {
+        self.inner.ctl_multiplexer.clone()
+    }
+
+    async fn connect(&self) -> Result<()> {
+        if !self.inner.is_running.swap(true, Ordering::SeqCst) {
+            let this = self.clone();
+            tokio::spawn(async move {
+                if let Err(error) = this.task().await {
+                    log_error!("gRPC client task error: {:?}", error);
+                }
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn disconnect(&self) -> Result<()> {
+        if self.inner.is_running.swap(false, Ordering::SeqCst) {
+            self.inner.shutdown_ctl.signal(()).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn ping(&self) -> Result<()> {
+        Ok(self.client()?.ping().await?)
+    }
+
+    async fn get_caps(&self) -> Result<Caps> {
+        rpc::kaspa::get_caps(self.client()?.as_ref()).await
+    }
+
+    async fn get_sync(&self) -> Result<bool> {
+        Ok(self.client()?.get_sync_status().await?)
+    }
+
+    async fn get_status(&self, connection: &Arc<Connection>) -> Result<Status> {
+        rpc::kaspa::get_status(
+            self.client()?.as_ref(),
+            &self.inner.last_metrics_data,
+            connection,
+        )
+        .await
+    }
+
+    fn trigger_abort(&self) -> Result<()> {
+        // gRPC has no abort facility; dropping the underlying
+        // connection results in the same reconnection sequence
+        let client = self.client()?;
+        tokio::spawn(async move {
+            client.disconnect().await.ok();
+        });
+        Ok(())
+    }
+}
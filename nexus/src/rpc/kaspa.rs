@@ -59,39 +59,7 @@ impl rpc::ClientT for Client {
     }
 
     async fn get_caps(&self) -> Result<Caps> {
-        let GetSystemInfoResponse {
-            version,
-            system_id,
-            git_hash,
-            cpu_physical_cores,
-            total_memory,
-            fd_limit,
-        } = self.client.get_system_info().await?;
-        let cpu_physical_cores = cpu_physical_cores as u64;
-        let fd_limit = fd_limit as u64;
-        // reduce node's fd_limit by FD_MARGIN to ensure
-        // the system has enough file descriptors for other
-        // tasks (peers, db, etc)
-        let fd_limit_actual = fd_limit.checked_sub(FD_MARGIN).unwrap_or(32);
-        // by default we assume that the node is able to accept
-        // 1024 connections per core (default NGINX worker configuration)
-        // TODO: this should be increased in the future once a custom
-        // proxy is implemented
-        let clients_limit = cpu_physical_cores * rpc::SOCKETS_PER_CORE;
-        let system_id = system_id
-            .and_then(|v| v[0..8].try_into().ok().map(u64::from_be_bytes))
-            .unwrap_or_default();
-        // let system_id_hex_string = format!("{:016x}", system_id);
-        let git_hash = git_hash.as_ref().map(ToHex::to_hex);
-        Ok(Caps {
-            version,
-            system_id,
-            git_hash,
-            total_memory,
-            cpu_physical_cores,
-            fd_limit: fd_limit_actual,
-            clients_limit,
-        })
+        get_caps(&self.client).await
     }
 
     async fn get_sync(&self) -> Result<bool> {
@@ -99,61 +67,137 @@ impl rpc::ClientT for Client {
     }
 
     async fn get_status(&self, connection: &Arc<Connection>) -> Result<Status> {
-        let metrics_response = self
-            .client
-            .get_metrics(true, true, true, true, true, false)
-            .await?;
-        let metrics_snapshot = {
-            let current_metrics_data = MetricsData::try_from(metrics_response)?;
-            let mut previous_metrics_data = self.last_metrics_data.lock().unwrap();
-            let metrics_snapshot = previous_metrics_data
-                .as_ref()
-                .map(|previous_metrics_data| {
-                    MetricsSnapshot::from((previous_metrics_data, &current_metrics_data))
-                })
-                .unwrap_or_default();
-            previous_metrics_data.replace(current_metrics_data);
-            metrics_snapshot
-        };
-        let GetBlockDagInfoResponse {
-            network: network_id,
-            block_count,
-            header_count,
-            tip_hashes,
-            difficulty,
-            past_median_time,
-            virtual_parent_hashes,
-            pruning_point_hash,
-            virtual_daa_score,
-            sink,
-        } = self.client.get_block_dag_info().await?;
-
-        let sid = connection.sid();
-        let uid = connection.uid();
-        let is_synced = connection.is_synced();
-
-        let kaspa_node_status = KaspaNodeStatus {
-            sid,
-            uid,
-            is_synced,
-            // block_dag_info,
-            metrics_snapshot,
-            network_id,
-            block_count,
-            header_count,
-            tip_hashes,
-            difficulty,
-            past_median_time,
-            virtual_parent_hashes,
-            pruning_point_hash,
-            virtual_daa_score,
-            sink,
-        };
-
-        Ok(kaspa_node_status.into())
+        get_status(&self.client, &self.last_metrics_data, connection).await
     }
 
     fn trigger_abort(&self) -> Result<()> {
         Ok(self.client.trigger_abort()?)
     }
 }
+
+/// Query the network of the node at `url` over a short-lived connection.
+pub async fn get_network(encoding: WrpcEncoding, url: &str) -> Result<NetworkId> {
+    let client = KaspaRpcClient::new(encoding, Some(url), None, None, None)?;
+    let options = ConnectOptions {
+        block_async_connect: true,
+        strategy: ConnectStrategy::Fallback,
+        url: Some(url.to_string()),
+        ..Default::default()
+    };
+    client.connect(Some(options)).await?;
+    let network = get_network_id(&client).await;
+    client.disconnect().await.ok();
+    network
+}
+
+/// Obtain the network of the node from any [`RpcApi`] implementation
+/// (shared by the wRPC and gRPC transports).
+pub(super) async fn get_network_id<R>(rpc: &R) -> Result<NetworkId>
+where
+    R: RpcApi + ?Sized,
+{
+    Ok(rpc.get_block_dag_info().await?.network)
+}
+
+/// Obtain node [`Caps`] from any [`RpcApi`] implementation
+/// (shared by the wRPC and gRPC transports).
+pub(super) async fn get_caps<R>(rpc: &R) -> Result<Caps>
+where
+    R: RpcApi + ?Sized,
+{
+    let GetSystemInfoResponse {
+        version,
+        system_id,
+        git_hash,
+        cpu_physical_cores,
+        total_memory,
+        fd_limit,
+    } = rpc.get_system_info().await?;
+    let cpu_physical_cores = cpu_physical_cores as u64;
+    let fd_limit = fd_limit as u64;
+    // reduce node's fd_limit by FD_MARGIN to ensure
+    // the system has enough file descriptors for other
+    // tasks (peers, db, etc)
+    let fd_limit_actual = fd_limit.checked_sub(FD_MARGIN).unwrap_or(32);
+    // by default we assume that the node is able to accept
+    // 1024 connections per core (default NGINX worker configuration)
+    // TODO: this should be increased in the future once a custom
+    // proxy is implemented
+    let clients_limit = cpu_physical_cores * rpc::SOCKETS_PER_CORE;
+    let system_id = system_id
+        .and_then(|v| v[0..8].try_into().ok().map(u64::from_be_bytes))
+        .unwrap_or_default();
+    // let system_id_hex_string = format!("{:016x}", system_id);
+    let git_hash = git_hash.as_ref().map(ToHex::to_hex);
+    Ok(Caps {
+        version,
+        system_id,
+        git_hash,
+        total_memory,
+        resident_set_size: None,
+        cpu_physical_cores,
+        fd_limit: fd_limit_actual,
+        clients_limit,
+    })
+}
+
+/// Obtain node [`Status`] from any [`RpcApi`] implementation
+/// (shared by the wRPC and gRPC transports).
+pub(super) async fn get_status<R>(
+    rpc: &R,
+    last_metrics_data: &Mutex<Option<MetricsData>>,
+    connection: &Arc<Connection>,
+) -> Result<Status>
+where
+    R: RpcApi + ?Sized,
+{
+    let metrics_response = rpc.get_metrics(true, true, true, true, true, false).await?;
+    let metrics_snapshot = {
+        let current_metrics_data = MetricsData::try_from(metrics_response)?;
+        let mut previous_metrics_data = last_metrics_data.lock().unwrap();
+        let metrics_snapshot = previous_metrics_data
+            .as_ref()
+            .map(|previous_metrics_data| {
+                MetricsSnapshot::from((previous_metrics_data, &current_metrics_data))
+            })
+            .unwrap_or_default();
+        previous_metrics_data.replace(current_metrics_data);
+        metrics_snapshot
+    };
+    let GetBlockDagInfoResponse {
+        network: network_id,
+        block_count,
+        header_count,
+        tip_hashes,
+        difficulty,
+        past_median_time,
+        virtual_parent_hashes,
+        pruning_point_hash,
+        virtual_daa_score,
+        sink,
+    } = rpc.get_block_dag_info().await?;
+
+    let sid = connection.sid();
+    let uid = connection.uid();
+    let is_synced = connection.is_synced();
+
+    let kaspa_node_status = KaspaNodeStatus {
+        sid,
+        uid,
+        is_synced,
+        // block_dag_info,
+        metrics_snapshot,
+        network_id,
+        block_count,
+        header_count,
+        tip_hashes,
+        difficulty,
+        past_median_time,
+        virtual_parent_hashes,
+        pruning_point_hash,
+        virtual_daa_score,
+        sink,
+    };
+
+    Ok(kaspa_node_status.into())
+}
@@ -1,3 +1,4 @@
+pub mod grpc;
 pub mod kaspa;
 pub mod sparkle;
 
@@ -9,6 +10,7 @@ const SOCKETS_PER_CORE: u64 = 1024;
 #[derive(Debug)]
 pub enum Client {
     Kaspa(kaspa::Client),
+    Grpc(grpc::Client),
     Sparkle(sparkle::Client),
 }
 
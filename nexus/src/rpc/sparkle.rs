@@ -5,9 +5,23 @@ use sparkle_rpc_client::prelude::SparkleRpcClient;
 pub struct Client {
     client: SparkleRpcClient,
     url: String,
+    // Sparkle does not expose a system id, so nodes are
+    // grouped by the machine they are configured on
+    system_id: u64,
+}
+
+impl Client {
+    pub fn try_new(encoding: WrpcEncoding, node: &NodeConfig) -> Result<Self> {
+        let client = SparkleRpcClient::try_new(Some(node.address()), Some(encoding))?;
+
+        Ok(Self {
+            client,
+            url: node.address().to_string(),
+            system_id: xxh3_64(node.fqdn.as_bytes()),
+        })
+    }
 }
 
-// #[async_trait]
 impl rpc::ClientT for Client {
     fn multiplexer(&self) -> Multiplexer<Ctl> {
         self.client.ctl_multiplexer()
@@ -25,31 +39,71 @@ impl rpc::ClientT for Client {
         Ok(())
     }
 
-    // async fn get_caps(&self) -> Result<Caps> {
-    //     let metrics = self.client.get_metrics(true, false, false, false, false).await?;
-    //     let process_metrics = metrics.process_metrics.ok_or(Error::Metrics)?;
+    async fn disconnect(&self) -> Result<()> {
+        Ok(self.client.disconnect().await?)
+    }
 
-    //     Ok(Caps {
-    //         resident_set_size: process_metrics.resident_set_size,
-    //         core_num: process_metrics.core_num,
-    //         fd_num: process_metrics.fd_num,
-    //     })
-    // }
+    async fn ping(&self) -> Result<()> {
+        self.client.ping().await?;
+        Ok(())
+    }
 
-    // async fn get_sync(&self) -> Result<bool> {
-    //     Ok(self.client.get_sync_status().await?)
-    // }
+    async fn get_caps(&self) -> Result<Caps> {
+        let metrics = self
+            .client
+            .get_metrics(true, false, false, false, false)
+            .await?;
+        let process_metrics = metrics.process_metrics.ok_or(Error::Metrics)?;
+        let cpu_physical_cores = process_metrics.core_num as u64;
+        // Sparkle does not report the process fd limit,
+        // use the limit configured in `Monitor.toml`
+        let fd_limit = Limits::fd();
+        let clients_limit = cpu_physical_cores * rpc::SOCKETS_PER_CORE;
 
-    // async fn get_active_connections(&self) -> Result<u64> {
+        Ok(Caps {
+            version: "sparkle".to_string(),
+            system_id: self.system_id,
+            git_hash: None,
+            // Sparkle reports process metrics only
+            total_memory: 0,
+            resident_set_size: Some(process_metrics.resident_set_size),
+            cpu_physical_cores,
+            fd_limit,
+            clients_limit,
+        })
+    }
 
-    //     let metrics = self.client.get_metrics(false, true, false, false, false).await?;
-    //     let connection_metrics = metrics.connection_metrics.ok_or(Error::Metrics)?;
-    //     let sockets =
-    //         connection_metrics.borsh_live_connections
-    //         + connection_metrics.json_live_connections
-    //         + connection_metrics.active_peers
-    //         ;
+    async fn get_sync(&self) -> Result<bool> {
+        Ok(self.client.get_sync_status().await?)
+    }
+
+    async fn get_status(&self, connection: &Arc<Connection>) -> Result<Status> {
+        let metrics = self
+            .client
+            .get_metrics(true, true, false, false, false)
+            .await?;
+        let process_metrics = metrics.process_metrics.ok_or(Error::Metrics)?;
+        let connection_metrics = metrics.connection_metrics.ok_or(Error::Metrics)?;
+
+        let sparkle_node_status = SparkleNodeStatus {
+            sid: connection.sid(),
+            uid: connection.uid(),
+            is_synced: connection.is_synced(),
+            network_id: connection.network_id(),
+            borsh_live_connections: connection_metrics.borsh_live_connections as u64,
+            json_live_connections: connection_metrics.json_live_connections as u64,
+            active_peers: connection_metrics.active_peers as u64,
+            resident_set_size: process_metrics.resident_set_size,
+            virtual_memory_size: process_metrics.virtual_memory_size,
+            core_num: process_metrics.core_num as u64,
+            cpu_usage: process_metrics.cpu_usage as f64,
+            fd_num: process_metrics.fd_num as u64,
+        };
 
-    //     Ok(sockets as u64)
-    // }
+        Ok(sparkle_node_status.into())
+    }
+
+    fn trigger_abort(&self) -> Result<()> {
+        Ok(self.client.trigger_abort()?)
+    }
 }
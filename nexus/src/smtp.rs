@@ -0,0 +1,307 @@
+use crate::imports::*;
+use mail_send::mail_builder::MessageBuilder;
+use mail_send::SmtpClientBuilder;
+use std::collections::VecDeque;
+use workflow_core::channel::MultiplexerChannel;
+
+// interval at which pending events are checked for delivery
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+// maximum number of undelivered events retained
+const MAX_PENDING_EVENTS: usize = 1024;
+const RATE_LIMIT_PERIOD: Duration = Duration::from_secs(60 * 60);
+// failed deliveries are retried with exponential backoff
+const RETRY_MIN: Duration = Duration::from_secs(10);
+const RETRY_MAX: Duration = Duration::from_secs(10 * 60);
+
+/// Sliding window rate limiter.
+struct RateLimit {
+    limit: usize,
+    period: Duration,
+    sent: VecDeque<Instant>,
+}
+
+impl RateLimit {
+    fn new(limit: usize, period: Duration) -> Self {
+        Self {
+            limit,
+            period,
+            sent: VecDeque::new(),
+        }
+    }
+
+    fn expire(&mut self) {
+        while self
+            .sent
+            .front()
+            .is_some_and(|sent| sent.elapsed() >= self.period)
+        {
+            self.sent.pop_front();
+        }
+    }
+
+    /// Whether an email can be sent without exceeding the limit.
+    fn is_available(&mut self) -> bool {
+        self.expire();
+        self.sent.len() < self.limit
+    }
+
+    /// Count a successfully sent email against the limit.
+    fn record(&mut self) {
+        self.sent.push_back(Instant::now());
+    }
+}
+
+fn compose(events: &[AlertEvent]) -> (String, String) {
+    let firing = events
+        .iter()
+        .filter(|event| event.state == AlertState::Firing)
+        .count();
+    let resolved = events.len() - firing;
+
+    let subject = match (firing, resolved) {
+        (0, resolved) => format!("[Kaspa Monitor] {resolved} alert(s) resolved"),
+        (firing, 0) => format!("[Kaspa Monitor] {firing} alert(s) firing"),
+        (firing, resolved) => {
+            format!("[Kaspa Monitor] {firing} alert(s) firing, {resolved} resolved")
+        }
+    };
+
+    let body = events
+        .iter()
+        .map(|event| {
+            let time = chrono::DateTime::from_timestamp_millis(event.timestamp as i64)
+                .map(|time| time.to_rfc3339())
+                .unwrap_or_default();
+            format!("{time} {event}")
+        })
+        .join("\n");
+
+    (subject, body)
+}
+
+struct Inner {
+    settings: SmtpSettings,
+    events: MultiplexerChannel<AlertEvent>,
+    shutdown_ctl: DuplexChannel<()>,
+}
+
+/// Alert notifier delivering [`AlertEvent`]s by email. Events are
+/// batched over the configured period and the number of emails
+/// is rate limited; events exceeding the limit are accumulated
+/// and delivered with the next email. Failed deliveries are retried
+/// with exponential backoff and do not count against the limit.
+#[derive(Clone)]
+pub struct SmtpNotifier {
+    inner: Arc<Inner>,
+}
+
+impl SmtpNotifier {
+    pub fn new(alerts: &Alerts, settings: SmtpSettings) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                settings,
+                events: alerts.multiplexer().channel(),
+                shutdown_ctl: DuplexChannel::oneshot(),
+            }),
+        }
+    }
+
+    async fn send(&self, events: &[AlertEvent], password: Option<&str>) -> Result<()> {
+        let settings = &self.inner.settings;
+        let (subject, body) = compose(events);
+
+        let message = MessageBuilder::new()
+            .from(settings.from.as_str())
+            .to(settings.to.iter().map(|to| to.as_str()).collect::<Vec<_>>())
+            .subject(subject)
+            .text_body(body);
+
+        let mut builder = SmtpClientBuilder::new(settings.host.as_str(), settings.port)
+            .implicit_tls(settings.security == SmtpSecurity::Tls);
+        if let (Some(username), Some(password)) = (&settings.username, password) {
+            builder = builder.credentials((username.as_str(), password));
+        }
+
+        match settings.security {
+            SmtpSecurity::None => builder.connect_plain().await?.send(message).await?,
+            _ => builder.connect().await?.send(message).await?,
+        }
+
+        Ok(())
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        let this = self.clone();
+        tokio::spawn(async move {
+            if let Err(error) = this.task().await {
+                log_error!("SMTP notifier task error: {:?}", error);
+            }
+        });
+
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        self.inner
+            .shutdown_ctl
+            .signal(())
+            .await
+            .expect("SMTP notifier shutdown signal error");
+        self.inner.events.close();
+
+        Ok(())
+    }
+
+    async fn task(self) -> Result<()> {
+        let events = self.inner.events.receiver.clone();
+        let shutdown_ctl_receiver = self.inner.shutdown_ctl.request.receiver.clone();
+        let shutdown_ctl_sender = self.inner.shutdown_ctl.response.sender.clone();
+
+        let settings = &self.inner.settings;
+        let password = if settings.username.is_some() {
+            load_smtp_password()
+                .map_err(|err| log_error!("SMTP: unable to load password: {err}"))
+                .ok()
+        } else {
+            None
+        };
+
+        let batch = settings.batch();
+        let mut rate_limit = RateLimit::new(settings.max_per_hour, RATE_LIMIT_PERIOD);
+        let mut pending = VecDeque::<AlertEvent>::new();
+        let mut batch_start: Option<Instant> = None;
+        let mut retry_delay = RETRY_MIN;
+        let mut retry_at: Option<Instant> = None;
+        let mut flush = interval(FLUSH_INTERVAL);
+
+        loop {
+            select! {
+                msg = events.recv().fuse() => {
+                    match msg {
+                        Ok(event) => {
+                            if pending.len() >= MAX_PENDING_EVENTS {
+                                log_warn!("SMTP: pending event limit reached, discarding oldest event");
+                                pending.pop_front();
+                            }
+                            pending.push_back(event);
+                            batch_start.get_or_insert_with(Instant::now);
+                        }
+                        Err(err) => {
+                            log_error!("SMTP: error while receiving alert events: {err}");
+                            break;
+                        }
+                    }
+                }
+
+                _ = flush.next().fuse() => {
+                    let ready = batch_start.is_some_and(|start| start.elapsed() >= batch)
+                        && !retry_at.is_some_and(|retry_at| Instant::now() < retry_at);
+                    if ready && rate_limit.is_available() {
+                        let events = pending.make_contiguous().to_vec();
+                        match self.send(&events, password.as_deref()).await {
+                            Ok(()) => {
+                                rate_limit.record();
+                                pending.clear();
+                                batch_start = None;
+                                retry_delay = RETRY_MIN;
+                                retry_at = None;
+                            }
+                            Err(err) => {
+                                // retained events are retried with the next email
+                                log_error!("SMTP: unable to send {} alert event(s), retrying in {:?}: {err}", events.len(), retry_delay);
+                                retry_at = Some(Instant::now() + retry_delay);
+                                retry_delay = (retry_delay * 2).min(RETRY_MAX);
+                            }
+                        }
+                    }
+                }
+
+                _ = shutdown_ctl_receiver.recv().fuse() => {
+                    break;
+                },
+            }
+        }
+
+        shutdown_ctl_sender.send(()).await.unwrap();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    /// Accept a single SMTP session, returning the received message.
+    async fn serve(listener: TcpListener) -> String {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut message = String::new();
+
+        writer.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+        while let Some(line) = lines.next_line().await.unwrap() {
+            let command = line.to_uppercase();
+            if command.starts_with("EHLO") || command.starts_with("HELO") {
+                writer.write_all(b"250 localhost\r\n").await.unwrap();
+            } else if command.starts_with("DATA") {
+                writer.write_all(b"354 go ahead\r\n").await.unwrap();
+                while let Some(line) = lines.next_line().await.unwrap() {
+                    if line == "." {
+                        break;
+                    }
+                    message.push_str(&line);
+                    message.push('\n');
+                }
+                writer.write_all(b"250 queued\r\n").await.unwrap();
+            } else if command.starts_with("QUIT") {
+                writer.write_all(b"221 bye\r\n").await.unwrap();
+                break;
+            } else {
+                writer.write_all(b"250 ok\r\n").await.unwrap();
+            }
+        }
+
+        message
+    }
+
+    #[tokio::test]
+    async fn test_delivery() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(serve(listener));
+
+        let settings = SmtpSettings {
+            enable: true,
+            host: "127.0.0.1".to_string(),
+            port,
+            security: SmtpSecurity::None,
+            username: None,
+            from: "monitor@localhost".to_string(),
+            to: vec!["ops@localhost".to_string()],
+            batch_sec: 0.0,
+            max_per_hour: 1,
+        };
+        let notifier = SmtpNotifier::new(&Alerts::default(), settings);
+        let event = AlertEvent {
+            kind: AlertKind::Offline,
+            severity: Severity::Critical,
+            state: AlertState::Firing,
+            uid: 1,
+            address: "wss://node.localhost".to_string(),
+            fqdn: "node.localhost".to_string(),
+            network: NetworkId::from_str("mainnet").unwrap(),
+            value: 1.0,
+            threshold: None,
+            timestamp: 0,
+            reason: None,
+        };
+        notifier.send(&[event], None).await.unwrap();
+
+        let message = server.await.unwrap();
+        assert!(message.contains("Subject: [Kaspa Monitor] 1 alert(s) firing"));
+        assert!(message.contains("wss://node.localhost"));
+    }
+}
@@ -0,0 +1,288 @@
+use crate::imports::*;
+
+// consecutive samples further apart than this many
+// sampling periods indicate that the node was offline
+const GAP_FACTOR: u64 = 3;
+
+struct Inner {
+    database: Arc<Database>,
+    interval: u64,
+    retention: Retention,
+    compaction: Duration,
+    // timestamp of the last stored sample for each node uid
+    last: Mutex<AHashMap<u64, u64>>,
+    samples: Channel<(u64, Arc<Status>)>,
+    shutdown_ctl: DuplexChannel<()>,
+}
+
+/// Persistent storage of node status history. Status updates are
+/// throttled to the configured sampling interval and written to the
+/// [`Database`] by a background task that also periodically applies
+/// the configured [`Retention`] policy.
+#[derive(Clone)]
+pub struct Storage {
+    inner: Arc<Inner>,
+}
+
+impl Storage {
+    pub fn try_new() -> Result<Self> {
+        let folder = StorageSettings::folder();
+        fs::create_dir_all(&folder)?;
+        let database = Arc::new(Database::open(&folder)?);
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                database,
+                interval: StorageSettings::interval().as_millis() as u64,
+                retention: StorageSettings::retention(),
+                compaction: StorageSettings::compaction(),
+                last: Mutex::new(AHashMap::new()),
+                samples: Channel::unbounded(),
+                shutdown_ctl: DuplexChannel::oneshot(),
+            }),
+        })
+    }
+
+    pub fn database(&self) -> &Arc<Database> {
+        &self.inner.database
+    }
+
+    /// Submit a status update for storage; updates received within
+    /// the sampling interval of the last stored sample are discarded.
+    pub fn ingest(&self, status: &Arc<Status>) {
+        let now = unixtime_as_millis_f64() as u64;
+        {
+            let mut last = self.inner.last.lock().unwrap();
+            let last = last.entry(status.uid()).or_default();
+            if now < *last + self.inner.interval {
+                return;
+            }
+            *last = now;
+        }
+
+        self.inner.samples.try_send((now, status.clone())).ok();
+    }
+
+    /// Obtain samples for the node `uid` in the `from..=to` time range.
+    pub async fn range(&self, uid: u64, from: u64, to: u64) -> Result<Vec<Sample>> {
+        let database = self.inner.database.clone();
+        Ok(spawn_blocking(move || database.range(uid, from, to)).await??)
+    }
+
+    /// Obtain samples for the node `uid` in the `from..=to` time range
+    /// reduced to the values of the given `metrics`, averaged into
+    /// `resolution` (msec) buckets if `resolution` is non-zero.
+    pub async fn history(
+        &self,
+        uid: u64,
+        from: u64,
+        to: u64,
+        metrics: &[Metric],
+        resolution: u64,
+    ) -> Result<Vec<HistorySample>> {
+        let samples = self.range(uid, from, to).await?;
+        Ok(history(&samples, metrics, resolution))
+    }
+
+    /// Build the sync state timeline of the node `uid`
+    /// over the entire stored history.
+    pub async fn timeline(&self, uid: u64) -> Result<Vec<TimelineSegment>> {
+        let now = unixtime_as_millis_f64() as u64;
+        let samples = self.range(uid, 0, now).await?;
+        let raw_cutoff = self.inner.retention.raw_cutoff(now);
+        let raw_gap = self.inner.interval * GAP_FACTOR;
+        let downsampled_gap = self.inner.retention.downsample_millis() * GAP_FACTOR;
+        let gap = |timestamp: u64| {
+            if timestamp < raw_cutoff {
+                downsampled_gap
+            } else {
+                raw_gap
+            }
+        };
+        Ok(timeline(&samples, now, gap))
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        let this = self.clone();
+        tokio::spawn(async move {
+            if let Err(error) = this.task().await {
+                log_error!("Storage task error: {:?}", error);
+            }
+        });
+
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        self.inner
+            .shutdown_ctl
+            .signal(())
+            .await
+            .expect("Storage shutdown signal error");
+
+        Ok(())
+    }
+
+    async fn compact(&self) -> Result<()> {
+        let database = self.inner.database.clone();
+        let retention = self.inner.retention.clone();
+        let now = unixtime_as_millis_f64() as u64;
+        let compaction = spawn_blocking(move || database.compact(&retention, now)).await??;
+        if compaction.downsampled > 0 || compaction.removed > 0 {
+            log_info!(
+                "Storage: downsampled {} and removed {} samples",
+                compaction.downsampled,
+                compaction.removed
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn task(self) -> Result<()> {
+        let samples = self.inner.samples.receiver.clone();
+        let shutdown_ctl_receiver = self.inner.shutdown_ctl.request.receiver.clone();
+        let shutdown_ctl_sender = self.inner.shutdown_ctl.response.sender.clone();
+
+        let mut compaction = interval(self.inner.compaction);
+
+        loop {
+            select! {
+                msg = samples.recv().fuse() => {
+                    match msg {
+                        Ok((timestamp, status)) => {
+                            let database = self.inner.database.clone();
+                            match spawn_blocking(move || database.store(timestamp, &status)).await {
+                                Ok(Err(err)) => log_error!("Storage: unable to store sample: {err}"),
+                                Err(err) => log_error!("Storage: {err}"),
+                                Ok(Ok(())) => {}
+                            }
+                        }
+                        Err(err) => {
+                            log_error!("Storage: error while receiving samples: {err}");
+                            break;
+                        }
+                    }
+                }
+
+                _ = compaction.next().fuse() => {
+                    if let Err(err) = self.compact().await {
+                        log_error!("Storage: compaction failure: {err}");
+                    }
+                }
+
+                _ = shutdown_ctl_receiver.recv().fuse() => {
+                    break;
+                },
+            }
+        }
+
+        shutdown_ctl_sender.send(()).await.unwrap();
+
+        Ok(())
+    }
+}
+
+fn history(samples: &[Sample], metrics: &[Metric], resolution: u64) -> Vec<HistorySample> {
+    let to_history_sample = |sample: &Sample| HistorySample {
+        timestamp: sample.timestamp,
+        is_synced: sample.status.is_synced(),
+        values: metrics
+            .iter()
+            .map(|metric| sample.status.metric(metric))
+            .collect(),
+    };
+
+    if resolution == 0 {
+        return samples.iter().map(to_history_sample).collect();
+    }
+
+    samples
+        .iter()
+        .chunk_by(|sample| sample.timestamp / resolution)
+        .into_iter()
+        .map(|(bucket, chunk)| {
+            let chunk = chunk.map(to_history_sample).collect::<Vec<_>>();
+            let values = (0..metrics.len())
+                .map(|index| {
+                    let values = chunk
+                        .iter()
+                        .filter_map(|sample| sample.values[index])
+                        .collect::<Vec<_>>();
+                    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+                })
+                .collect();
+
+            HistorySample {
+                timestamp: bucket * resolution,
+                is_synced: chunk.iter().all(|sample| sample.is_synced),
+                values,
+            }
+        })
+        .collect()
+}
+
+fn timeline(samples: &[Sample], now: u64, gap: impl Fn(u64) -> u64) -> Vec<TimelineSegment> {
+    let state = |sample: &Sample| {
+        if sample.status.is_synced() {
+            TimelineState::Synced
+        } else {
+            TimelineState::Syncing
+        }
+    };
+
+    let mut segments = Vec::new();
+    let mut iter = samples.iter();
+    let Some(first) = iter.next() else {
+        return segments;
+    };
+
+    let mut current = TimelineSegment {
+        from: first.timestamp,
+        to: first.timestamp,
+        state: state(first),
+    };
+
+    for sample in iter {
+        if sample.timestamp - current.to > gap(sample.timestamp) {
+            let offline = TimelineSegment {
+                from: current.to,
+                to: sample.timestamp,
+                state: TimelineState::Offline,
+            };
+            segments.push(std::mem::replace(
+                &mut current,
+                TimelineSegment {
+                    from: sample.timestamp,
+                    to: sample.timestamp,
+                    state: state(sample),
+                },
+            ));
+            segments.push(offline);
+        } else if state(sample) != current.state {
+            current.to = sample.timestamp;
+            segments.push(std::mem::replace(
+                &mut current,
+                TimelineSegment {
+                    from: sample.timestamp,
+                    to: sample.timestamp,
+                    state: state(sample),
+                },
+            ));
+        } else {
+            current.to = sample.timestamp;
+        }
+    }
+
+    let last = current.to;
+    segments.push(current);
+    if now.saturating_sub(last) > gap(now) {
+        segments.push(TimelineSegment {
+            from: last,
+            to: now,
+            state: TimelineState::Offline,
+        });
+    }
+
+    segments
+}
@@ -0,0 +1,85 @@
+use crate::imports::*;
+
+/// Notification filter of a single client context, created by
+/// the `Subscribe` RPC op. Empty filter lists match everything.
+pub struct Subscription {
+    networks: AHashSet<NetworkId>,
+    services: AHashSet<String>,
+    uids: AHashSet<u64>,
+    update_kinds: AHashSet<UpdateKind>,
+    // minimum interval between `Status` updates of the same node
+    min_interval: Duration,
+    // time at which the last `Status` update was posted for each node uid
+    posted: Mutex<AHashMap<u64, Instant>>,
+}
+
+impl From<SubscribeRequest> for Subscription {
+    fn from(request: SubscribeRequest) -> Self {
+        let SubscribeRequest {
+            networks,
+            services,
+            uids,
+            update_kinds,
+            min_interval,
+        } = request;
+
+        Self {
+            networks: networks.into_iter().collect(),
+            services: services
+                .into_iter()
+                .map(|service| service.to_lowercase())
+                .collect(),
+            uids: uids.into_iter().collect(),
+            update_kinds: update_kinds.into_iter().collect(),
+            min_interval: Duration::from_millis(min_interval),
+            posted: Default::default(),
+        }
+    }
+}
+
+impl Subscription {
+    /// Check if the `update` should be posted to the subscriber. `node` is
+    /// the config of the node the update refers to; updates referring to
+    /// nodes that are no longer known (such as `NodeRemoved`) are matched
+    /// by uid only. `Status` updates of a node are rate-limited to one per
+    /// `min_interval`.
+    pub fn accepts(&self, update: &Update, node: Option<&NodeConfig>) -> bool {
+        if !self.update_kinds.is_empty() && !self.update_kinds.contains(&update.kind()) {
+            return false;
+        }
+
+        if let Some(uid) = update.uid() {
+            if !self.uids.is_empty() && !self.uids.contains(&uid) {
+                return false;
+            }
+        }
+
+        if let Some(node) = node {
+            if !self.networks.is_empty() && !self.networks.contains(&node.network) {
+                return false;
+            }
+            if !self.services.is_empty() && !self.services.contains(&node.service().to_string()) {
+                return false;
+            }
+        }
+
+        match update {
+            Update::Status { status } if !self.min_interval.is_zero() => {
+                let mut posted = self.posted.lock().unwrap();
+                let now = Instant::now();
+                match posted.get(&status.uid()) {
+                    Some(last) if now.duration_since(*last) < self.min_interval => false,
+                    _ => {
+                        posted.insert(status.uid(), now);
+                        true
+                    }
+                }
+            }
+            Update::NodeRemoved { uid } => {
+                self.posted.lock().unwrap().remove(uid);
+                true
+            }
+            _ => true,
+        }
+    }
+}
@@ -0,0 +1,538 @@
+use crate::imports::*;
+
+const DAY: u64 = 24 * 60 * 60 * 1000;
+
+/// Time range in unix msec, `to` is `None` while the range is open.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+struct Span {
+    from: u64,
+    to: Option<u64>,
+}
+
+impl Span {
+    fn end(&self, now: u64) -> u64 {
+        self.to.unwrap_or(now).max(self.from)
+    }
+
+    fn duration(&self, now: u64) -> u64 {
+        self.end(now) - self.from
+    }
+
+    /// Overlap of this span with `from..to` in msec.
+    fn overlap(&self, from: u64, to: u64, now: u64) -> u64 {
+        self.end(now).min(to).saturating_sub(self.from.max(from))
+    }
+}
+
+/// Monitoring periods and outages of a node or machine.
+#[derive(Default, Serialize, Deserialize)]
+struct History {
+    name: String,
+    monitored: Vec<Span>,
+    outages: Vec<(Span, OutageCause)>,
+}
+
+impl History {
+    fn is_monitored(&self) -> bool {
+        self.monitored.last().is_some_and(|span| span.to.is_none())
+    }
+
+    /// Cause of the ongoing outage, if any.
+    fn cause(&self) -> Option<OutageCause> {
+        self.outages
+            .last()
+            .filter(|(span, _)| span.to.is_none())
+            .map(|(_, cause)| *cause)
+    }
+
+    fn open(&mut self, time: u64) {
+        if !self.is_monitored() {
+            self.monitored.push(Span {
+                from: time,
+                to: None,
+            });
+        }
+    }
+
+    /// Record the availability at `time`, `cause` is `None` if available.
+    fn transition(&mut self, cause: Option<OutageCause>, time: u64) {
+        if self.cause() == cause {
+            return;
+        }
+        self.close_outage(time);
+        if let Some(cause) = cause {
+            self.outages.push((
+                Span {
+                    from: time,
+                    to: None,
+                },
+                cause,
+            ));
+        }
+    }
+
+    fn close_outage(&mut self, time: u64) {
+        if let Some((span, _)) = self.outages.last_mut() {
+            if span.to.is_none() {
+                span.to = Some(time.max(span.from));
+            }
+        }
+    }
+
+    fn close(&mut self, time: u64) {
+        self.close_outage(time);
+        if let Some(span) = self.monitored.last_mut() {
+            if span.to.is_none() {
+                span.to = Some(time.max(span.from));
+            }
+        }
+    }
+
+    /// Discard ranges that ended before `cutoff`.
+    fn prune(&mut self, cutoff: u64) {
+        self.monitored
+            .retain(|span| !matches!(span.to, Some(to) if to < cutoff));
+        self.outages
+            .retain(|(span, _)| !matches!(span.to, Some(to) if to < cutoff));
+    }
+
+    fn outages(&self, now: u64, min_outage: u64) -> impl Iterator<Item = &(Span, OutageCause)> {
+        self.outages
+            .iter()
+            .filter(move |(span, _)| span.duration(now) >= min_outage)
+    }
+
+    /// Percentage of the monitored time within the last `period`
+    /// msec during which the node or machine was available.
+    fn uptime(&self, period: u64, now: u64, min_outage: u64) -> Option<f64> {
+        let from = now.saturating_sub(period);
+        let monitored = self
+            .monitored
+            .iter()
+            .map(|span| span.overlap(from, now, now))
+            .sum::<u64>();
+        if monitored == 0 {
+            return None;
+        }
+        let down = self
+            .outages(now, min_outage)
+            .map(|(span, _)| span.overlap(from, now, now))
+            .sum::<u64>();
+        Some(100.0 * monitored.saturating_sub(down) as f64 / monitored as f64)
+    }
+
+    fn availability(&self, id: u64, now: u64, min_outage: u64) -> Availability {
+        let from = now.saturating_sub(30 * DAY);
+        let outages = self
+            .outages(now, min_outage)
+            .filter(|(span, _)| span.end(now) > from)
+            .map(|(span, cause)| Outage {
+                start: span.from,
+                end: span.to,
+                duration: span.duration(now),
+                cause: *cause,
+            })
+            .collect();
+
+        Availability {
+            id,
+            name: self.name.clone(),
+            uptime_24h: self.uptime(DAY, now, min_outage),
+            uptime_7d: self.uptime(7 * DAY, now, min_outage),
+            uptime_30d: self.uptime(30 * DAY, now, min_outage),
+            outages,
+        }
+    }
+}
+
+/// Histories of nodes (by uid) and machines (by sid) as saved to disk.
+#[derive(Default, Serialize, Deserialize)]
+struct Histories {
+    // unix time in msec at which the histories were saved
+    saved: u64,
+    nodes: HashMap<u64, History>,
+    machines: HashMap<u64, History>,
+}
+
+/// Last reported state of a node connection.
+#[derive(Default, Clone)]
+struct State {
+    // fqdn of the node, naming its machine
+    fqdn: String,
+    sid: u64,
+    connected: bool,
+    online: bool,
+    synced: bool,
+    delegate: Option<u64>,
+}
+
+#[derive(Default)]
+struct Inner {
+    histories: Histories,
+    states: AHashMap<u64, State>,
+}
+
+impl Inner {
+    fn cause(&self, state: &State) -> Option<OutageCause> {
+        if !state.connected {
+            return Some(OutageCause::Disconnect);
+        }
+        if !state.online {
+            return Some(OutageCause::MetricsError);
+        }
+
+        // delegators are synced if their delegate is
+        let mut synced = state.synced;
+        let mut delegate = state.delegate;
+        let mut depth = 0;
+        while let Some(state) = delegate.and_then(|uid| self.states.get(&uid)) {
+            if depth == self.states.len() {
+                break;
+            }
+            synced = state.online && state.synced;
+            delegate = state.delegate;
+            depth += 1;
+        }
+
+        (!synced).then_some(OutageCause::NotSynced)
+    }
+
+    /// Record the availability of nodes and machines at `time`. A machine
+    /// is available if any of its nodes is, otherwise the outage cause is
+    /// that of the node closest to being available.
+    fn apply(&mut self, time: u64) {
+        let mut machines = AHashMap::<u64, (Option<OutageCause>, &str)>::new();
+        for (uid, state) in self.states.iter() {
+            let cause = self.cause(state);
+            if let Some(history) = self.histories.nodes.get_mut(uid) {
+                history.transition(cause, time);
+            }
+            if state.sid != 0 {
+                machines
+                    .entry(state.sid)
+                    .and_modify(|(current, _)| {
+                        *current = current
+                            .zip(cause)
+                            .map(|(current, cause)| current.max(cause))
+                    })
+                    .or_insert((cause, state.fqdn.as_str()));
+            }
+        }
+
+        for (sid, history) in self.histories.machines.iter_mut() {
+            if !machines.contains_key(sid) {
+                history.close(time);
+            }
+        }
+        for (sid, (cause, fqdn)) in machines {
+            let history = self.histories.machines.entry(sid).or_default();
+            if history.name.is_empty() {
+                fqdn.clone_into(&mut history.name);
+            }
+            history.open(time);
+            history.transition(cause, time);
+        }
+    }
+}
+
+impl Histories {
+    /// Close ranges left open by a monitor that was not shut
+    /// down cleanly at the time the histories were last saved.
+    fn close(&mut self) {
+        let saved = self.saved;
+        for history in self.nodes.values_mut().chain(self.machines.values_mut()) {
+            history.close(saved);
+        }
+    }
+
+    /// Discard ranges that ended before `cutoff` and
+    /// histories left without any monitoring periods.
+    fn prune(&mut self, cutoff: u64) {
+        for history in self.nodes.values_mut().chain(self.machines.values_mut()) {
+            history.prune(cutoff);
+        }
+        self.nodes
+            .retain(|_, history| !history.monitored.is_empty());
+        self.machines
+            .retain(|_, history| !history.monitored.is_empty());
+    }
+}
+
+/// Uptime accounting of node connections and machines. Tracks the periods
+/// during which nodes are disconnected, failing to report their status
+/// or not synced, based on [`Event::State`] updates of the connections.
+///
+/// Uptime is reported relative to the time a node or machine was
+/// monitored: periods during which the monitor was not running or
+/// the node was not configured do not count as outages. The history is
+/// saved to [`UptimeSettings::path`]; ranges left open by a monitor that
+/// was not shut down cleanly end at the time the history was last saved.
+pub struct Uptime {
+    path: PathBuf,
+    inner: Mutex<Inner>,
+}
+
+impl Uptime {
+    pub fn try_new() -> Result<Self> {
+        let path = UptimeSettings::path();
+        let mut histories = if path.exists() {
+            serde_json::from_str::<Histories>(&fs::read_to_string(&path)?)?
+        } else {
+            Histories::default()
+        };
+
+        histories.close();
+
+        Ok(Self {
+            path,
+            inner: Mutex::new(Inner {
+                histories,
+                states: AHashMap::new(),
+            }),
+        })
+    }
+
+    /// Start monitoring the node of `connection` in its current state.
+    pub fn register(&self, connection: &Connection, time: u64) {
+        let uid = connection.uid();
+        let node = connection.node();
+        let state = State {
+            fqdn: node.fqdn.clone(),
+            sid: connection.sid(),
+            connected: connection.connected(),
+            online: connection.online(),
+            synced: connection.is_synced(),
+            delegate: connection.bound_delegate().map(|delegate| delegate.uid()),
+        };
+        let mut inner = self.inner.lock().unwrap();
+        inner.states.insert(uid, state);
+        let history = inner.histories.nodes.entry(uid).or_default();
+        node.address.clone_into(&mut history.name);
+        history.open(time);
+        inner.apply(time);
+    }
+
+    /// Stop monitoring the node `uid`.
+    pub fn remove(&self, uid: u64, time: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.states.remove(&uid).is_some() {
+            if let Some(history) = inner.histories.nodes.get_mut(&uid) {
+                history.close(time);
+            }
+            inner.apply(time);
+        }
+    }
+
+    /// Record a state change of the node `uid` at `time`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &self,
+        uid: u64,
+        sid: u64,
+        connected: bool,
+        online: bool,
+        synced: bool,
+        delegate: Option<u64>,
+        time: u64,
+    ) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(state) = inner.states.get_mut(&uid) {
+            state.sid = sid;
+            state.connected = connected;
+            state.online = online;
+            state.synced = synced;
+            state.delegate = delegate;
+            inner.apply(time);
+        }
+    }
+
+    /// Uptime percentages over the last 24 hours, 7 days and
+    /// 30 days and outages within the last 30 days. Outages shorter
+    /// than [`UptimeSettings::min_outage`] are not counted.
+    pub fn report(&self) -> UptimeReport {
+        let now = unixtime_as_millis_f64() as u64;
+        let min_outage = UptimeSettings::min_outage().as_millis() as u64;
+        let inner = self.inner.lock().unwrap();
+        let report = |histories: &HashMap<u64, History>| {
+            histories
+                .iter()
+                .map(|(id, history)| history.availability(*id, now, min_outage))
+                .sorted_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)))
+                .collect::<Vec<_>>()
+        };
+
+        UptimeReport {
+            timestamp: now,
+            nodes: report(&inner.histories.nodes),
+            machines: report(&inner.histories.machines),
+        }
+    }
+
+    /// Save the history, discarding ranges older than
+    /// [`UptimeSettings::retention`].
+    pub fn save(&self) -> Result<()> {
+        let json = {
+            let mut inner = self.inner.lock().unwrap();
+            let now = unixtime_as_millis_f64() as u64;
+            let cutoff = now.saturating_sub(UptimeSettings::retention().as_millis() as u64);
+            let histories = &mut inner.histories;
+            histories.saved = now;
+            histories.prune(cutoff);
+            serde_json::to_string(histories)?
+        };
+
+        let temp = self.path.with_extension("tmp");
+        fs::write(&temp, json)?;
+        fs::rename(&temp, &self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(from: u64, to: Option<u64>) -> Span {
+        Span { from, to }
+    }
+
+    fn history(monitored: Vec<Span>, outages: Vec<Span>) -> History {
+        History {
+            name: "node".to_string(),
+            monitored,
+            outages: outages
+                .into_iter()
+                .map(|span| (span, OutageCause::Disconnect))
+                .collect(),
+        }
+    }
+
+    fn state(fqdn: &str, sid: u64, connected: bool) -> State {
+        State {
+            fqdn: fqdn.to_string(),
+            sid,
+            connected,
+            online: connected,
+            synced: connected,
+            delegate: None,
+        }
+    }
+
+    #[test]
+    fn test_span_overlap() {
+        let closed = span(100, Some(200));
+        assert_eq!(closed.overlap(150, 300, 1000), 50);
+        assert_eq!(closed.overlap(0, 100, 1000), 0);
+        assert_eq!(closed.overlap(300, 400, 1000), 0);
+
+        // open spans end at `now`
+        let open = span(100, None);
+        assert_eq!(open.overlap(0, 1000, 400), 300);
+        assert_eq!(open.duration(400), 300);
+    }
+
+    #[test]
+    fn test_uptime_counts_outages_within_period() {
+        let history = history(vec![span(0, Some(1000))], vec![span(200, Some(300))]);
+        assert_eq!(history.uptime(1000, 1000, 0), Some(90.0));
+        // only the monitored part of the period counts
+        assert_eq!(history.uptime(2000, 1000, 0), Some(90.0));
+        // the outage is outside of the last 500 msec
+        assert_eq!(history.uptime(500, 1000, 0), Some(100.0));
+    }
+
+    #[test]
+    fn test_uptime_ignores_short_outages() {
+        let history = history(
+            vec![span(0, Some(1000))],
+            vec![span(200, Some(210)), span(500, Some(600))],
+        );
+        assert_eq!(history.uptime(1000, 1000, 50), Some(90.0));
+        assert_eq!(history.uptime(1000, 1000, 0), Some(89.0));
+        assert_eq!(history.outages(1000, 50).count(), 1);
+    }
+
+    #[test]
+    fn test_uptime_is_unknown_without_monitoring() {
+        let history = history(vec![span(0, Some(100))], vec![]);
+        assert_eq!(history.uptime(500, 1000, 0), None);
+    }
+
+    #[test]
+    fn test_prune_discards_expired_ranges() {
+        let mut histories = Histories::default();
+        histories.nodes.insert(
+            1,
+            history(
+                vec![span(0, Some(100)), span(200, None)],
+                vec![span(50, Some(60)), span(250, Some(300))],
+            ),
+        );
+        histories
+            .nodes
+            .insert(2, history(vec![span(0, Some(100))], vec![]));
+
+        histories.prune(150);
+
+        let node = &histories.nodes[&1];
+        assert_eq!(node.monitored.len(), 1);
+        assert_eq!(node.monitored[0].from, 200);
+        assert_eq!(node.outages.len(), 1);
+        assert_eq!(node.outages[0].0.from, 250);
+        assert!(!histories.nodes.contains_key(&2));
+    }
+
+    #[test]
+    fn test_unclean_shutdown_closes_ranges_at_last_save() {
+        let mut histories = Histories {
+            saved: 500,
+            ..Default::default()
+        };
+        histories
+            .nodes
+            .insert(1, history(vec![span(0, None)], vec![span(400, None)]));
+        histories
+            .machines
+            .insert(2, history(vec![span(100, None)], vec![]));
+
+        histories.close();
+
+        let node = &histories.nodes[&1];
+        assert_eq!(node.monitored[0].to, Some(500));
+        assert_eq!(node.outages[0].0.to, Some(500));
+        assert_eq!(histories.machines[&2].monitored[0].to, Some(500));
+        assert!(!node.is_monitored());
+    }
+
+    #[test]
+    fn test_machine_is_available_if_any_node_is() {
+        let mut inner = Inner::default();
+        inner.states.insert(1, state("a.example.com", 7, true));
+        inner.states.insert(2, state("a.example.com", 7, false));
+        inner.apply(100);
+
+        let machine = &inner.histories.machines[&7];
+        assert_eq!(machine.name, "a.example.com");
+        assert!(machine.is_monitored());
+        assert_eq!(machine.cause(), None);
+
+        inner.states.get_mut(&1).unwrap().connected = false;
+        inner.apply(200);
+        let machine = &inner.histories.machines[&7];
+        assert_eq!(machine.cause(), Some(OutageCause::Disconnect));
+        assert_eq!(machine.outages[0].0.from, 200);
+    }
+
+    #[test]
+    fn test_machine_without_nodes_is_closed() {
+        let mut inner = Inner::default();
+        inner.states.insert(1, state("a.example.com", 7, true));
+        inner.apply(100);
+        inner.states.remove(&1);
+        inner.apply(300);
+
+        let machine = &inner.histories.machines[&7];
+        assert!(!machine.is_monitored());
+        assert_eq!(machine.monitored[0].to, Some(300));
+    }
+}
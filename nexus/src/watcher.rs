@@ -0,0 +1,242 @@
+use crate::imports::*;
+use std::time::SystemTime;
+
+/// Node whose transport settings changed between two configs.
+#[derive(Clone, Debug)]
+pub struct NodeChange {
+    pub previous: Arc<NodeConfig>,
+    pub next: Arc<NodeConfig>,
+}
+
+impl Display for NodeChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.next)?;
+        if self.previous.transport_kind() != self.next.transport_kind() {
+            write!(
+                f,
+                " transport: {} -> {}",
+                self.previous.transport_kind(),
+                self.next.transport_kind()
+            )?;
+        }
+        if self.previous.tls() != self.next.tls() {
+            write!(f, " tls: {} -> {}", self.previous.tls(), self.next.tls())?;
+        }
+        if self.previous.service() != self.next.service() {
+            write!(
+                f,
+                " service: {} -> {}",
+                self.previous.service(),
+                self.next.service()
+            )?;
+        }
+        if self.previous.network != self.next.network {
+            write!(
+                f,
+                " network: {} -> {}",
+                self.previous.network, self.next.network
+            )?;
+        }
+        if self.previous.fqdn != self.next.fqdn {
+            write!(f, " fqdn: {} -> {}", self.previous.fqdn, self.next.fqdn)?;
+        }
+        if self.previous.is_enabled() != self.next.is_enabled() {
+            write!(
+                f,
+                " enable: {} -> {}",
+                self.previous.is_enabled(),
+                self.next.is_enabled()
+            )?;
+        }
+        if self.previous.bias() != self.next.bias() {
+            write!(
+                f,
+                " bias: {} -> {}",
+                self.previous.bias.unwrap_or(1.0),
+                self.next.bias.unwrap_or(1.0)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Difference between two node sets, matching nodes by uid.
+#[derive(Clone, Debug, Default)]
+pub struct ConfigDiff {
+    pub added: Vec<Arc<NodeConfig>>,
+    pub removed: Vec<Arc<NodeConfig>>,
+    pub changed: Vec<NodeChange>,
+}
+
+impl ConfigDiff {
+    /// Compute the difference between the `previous` and `next` node
+    /// sets. Fails if `next` contains multiple nodes with the same uid
+    /// or a node using a transport its service does not support.
+    pub fn try_new(previous: &[Arc<NodeConfig>], next: &[Arc<NodeConfig>]) -> Result<Self> {
+        let mut next_by_uid = AHashMap::with_capacity(next.len());
+        for node in next.iter() {
+            if next_by_uid.insert(node.uid(), node).is_some() {
+                return Err(Error::config(format!("Duplicate node: {node}")));
+            }
+            if node.service() == ServiceKind::Sparkle
+                && node.transport_kind() == TransportKind::Grpc
+            {
+                return Err(Error::config(format!(
+                    "gRPC transport is not supported by Sparkle: {node}"
+                )));
+            }
+        }
+        let previous_by_uid = previous
+            .iter()
+            .map(|node| (node.uid(), node))
+            .collect::<AHashMap<_, _>>();
+
+        let added = next
+            .iter()
+            .filter(|node| !previous_by_uid.contains_key(&node.uid()))
+            .cloned()
+            .collect();
+
+        let removed = previous
+            .iter()
+            .filter(|node| !next_by_uid.contains_key(&node.uid()))
+            .cloned()
+            .collect();
+
+        let changed = next
+            .iter()
+            .filter_map(|next| {
+                let previous = previous_by_uid.get(&next.uid())?;
+                let changed =
+                    previous.is_changed(next) || previous.is_enabled() != next.is_enabled();
+                changed.then(|| NodeChange {
+                    previous: (*previous).clone(),
+                    next: next.clone(),
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            added,
+            removed,
+            changed,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl Display for ConfigDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} added, {} removed, {} changed",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len()
+        )?;
+        for node in self.added.iter() {
+            write!(
+                f,
+                "\n  + {node} ({}, {})",
+                node.transport_kind(),
+                node.network
+            )?;
+        }
+        for node in self.removed.iter() {
+            write!(f, "\n  - {node}")?;
+        }
+        for change in self.changed.iter() {
+            write!(f, "\n  ~ {change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Watches config files for changes by polling
+/// their modification time at [`ConfigSettings::poll`].
+pub struct ConfigWatcher {
+    modified: Mutex<AHashMap<PathBuf, Option<SystemTime>>>,
+}
+
+impl ConfigWatcher {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let modified = paths
+            .into_iter()
+            .map(|path| {
+                let modified = Self::modified(&path);
+                (path, modified)
+            })
+            .collect();
+
+        Self {
+            modified: Mutex::new(modified),
+        }
+    }
+
+    fn modified(path: &Path) -> Option<SystemTime> {
+        fs::metadata(path).and_then(|meta| meta.modified()).ok()
+    }
+
+    /// Returns paths of watched files that were created, modified
+    /// or removed since the last call.
+    pub fn changed(&self) -> Vec<PathBuf> {
+        let mut modified = self.modified.lock().unwrap();
+        modified
+            .iter_mut()
+            .filter_map(|(path, last)| {
+                let current = Self::modified(path);
+                (current != *last).then(|| {
+                    *last = current;
+                    path.clone()
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(address: &str, network: &str) -> Arc<NodeConfig> {
+        let transport = Transport {
+            kind: TransportKind::WrpcBorsh,
+            tls: true,
+            template: String::new(),
+        };
+        NodeConfig::new(
+            &ServiceKind::Kaspa,
+            NetworkId::from_str(network).unwrap(),
+            &transport,
+            "node.example.com",
+            address,
+        )
+    }
+
+    #[test]
+    fn test_unchanged_node_set_is_empty() {
+        let nodes = [node("wss://a.example.com", "mainnet")];
+        let diff = ConfigDiff::try_new(&nodes, &nodes).unwrap();
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_changed_network_is_reported() {
+        let previous = node("wss://a.example.com", "mainnet");
+        let next = node("wss://a.example.com", "testnet-10");
+        let diff = ConfigDiff::try_new(&[previous], &[next.clone()]).unwrap();
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].next.uid(), next.uid());
+    }
+
+    #[test]
+    fn test_duplicate_node_is_rejected() {
+        let a = node("wss://a.example.com", "mainnet");
+        assert!(ConfigDiff::try_new(&[], &[a.clone(), a]).is_err());
+    }
+}
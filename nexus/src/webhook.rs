@@ -0,0 +1,318 @@
+use crate::imports::*;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use workflow_core::channel::MultiplexerChannel;
+
+// interval at which queued deliveries are checked
+const DELIVERY_INTERVAL: Duration = Duration::from_secs(1);
+// time after which a delivery pass stops attempting further deliveries
+// (remaining deliveries are attempted with the next pass)
+const DELIVERY_PASS_LIMIT: Duration = Duration::from_secs(15);
+const SIGNATURE_HEADER: &str = "X-Kaspa-Monitor-Signature";
+
+/// JSON payload posted to webhook endpoints.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    pub uid: String,
+    pub fqdn: String,
+    pub address: String,
+    pub network: String,
+    pub rule: AlertKind,
+    pub severity: Severity,
+    pub state: AlertState,
+    pub value: f64,
+    pub threshold: Option<f64>,
+    // unix time in msec
+    pub timestamp: u64,
+    pub reason: Option<String>,
+}
+
+impl From<&AlertEvent> for WebhookPayload {
+    fn from(event: &AlertEvent) -> Self {
+        Self {
+            uid: format!("{:016x}", event.uid),
+            fqdn: event.fqdn.clone(),
+            address: event.address.clone(),
+            network: event.network.to_string(),
+            rule: event.kind,
+            severity: event.severity,
+            state: event.state,
+            value: event.value,
+            threshold: event.threshold,
+            timestamp: event.timestamp,
+            reason: event.reason.clone(),
+        }
+    }
+}
+
+/// Queued delivery of a payload to a single endpoint.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Delivery {
+    url: String,
+    body: String,
+    attempts: u32,
+    // unix time in msec
+    next_attempt: u64,
+}
+
+/// Delivery state of an endpoint. Once a delivery to the endpoint
+/// fails, its queued deliveries are deferred until `next_attempt`.
+#[derive(Default)]
+struct Endpoint {
+    failures: u32,
+    // unix time in msec
+    next_attempt: u64,
+}
+
+/// Bounded delivery queue persisted as JSON so that
+/// undelivered alerts survive monitor restarts.
+struct Queue {
+    deliveries: VecDeque<Delivery>,
+    limit: usize,
+    path: PathBuf,
+}
+
+impl Queue {
+    fn load(path: PathBuf, limit: usize) -> Self {
+        let deliveries = fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| {
+                serde_json::from_str::<VecDeque<Delivery>>(&json)
+                    .map_err(|err| log_error!("Webhook: unable to load delivery queue: {err}"))
+                    .ok()
+            })
+            .unwrap_or_default();
+
+        let mut queue = Self {
+            deliveries,
+            limit,
+            path,
+        };
+        queue.truncate();
+        queue
+    }
+
+    fn store(&self) {
+        let result = serde_json::to_string(&self.deliveries)
+            .map_err(Error::from)
+            .and_then(|json| Ok(fs::write(&self.path, json)?));
+        if let Err(err) = result {
+            log_error!("Webhook: unable to store delivery queue: {err}");
+        }
+    }
+
+    fn truncate(&mut self) {
+        if self.deliveries.len() > self.limit {
+            let dropped = self.deliveries.len() - self.limit;
+            log_warn!("Webhook: delivery queue is full, dropping {dropped} oldest deliveries");
+            self.deliveries.drain(..dropped);
+        }
+    }
+
+    fn push(&mut self, delivery: Delivery) {
+        self.deliveries.push_back(delivery);
+        self.truncate();
+    }
+}
+
+fn sign(secret: &[u8], body: &str) -> Result<String> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret).map_err(|err| Error::custom(err.to_string()))?;
+    mac.update(body.as_bytes());
+    Ok(format!(
+        "sha256={}",
+        hex::encode(mac.finalize().into_bytes())
+    ))
+}
+
+struct Inner {
+    settings: &'static WebhookSettings,
+    events: MultiplexerChannel<AlertEvent>,
+    shutdown_ctl: DuplexChannel<()>,
+}
+
+/// Alert notifier posting [`AlertEvent`]s as JSON to the configured
+/// endpoints. Failed deliveries are retried with exponential backoff
+/// from a bounded queue persisted in the global config folder; an
+/// endpoint that fails is skipped until its next retry time.
+/// Payloads are optionally signed with HMAC-SHA256.
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    inner: Arc<Inner>,
+}
+
+impl WebhookNotifier {
+    pub fn new(alerts: &Alerts) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                settings: WebhookSettings::get(),
+                events: alerts.multiplexer().channel(),
+                shutdown_ctl: DuplexChannel::oneshot(),
+            }),
+        }
+    }
+
+    async fn post(
+        &self,
+        client: &reqwest::Client,
+        secret: Option<&[u8]>,
+        delivery: &Delivery,
+    ) -> Result<()> {
+        let mut request = client
+            .post(&delivery.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(delivery.body.clone());
+
+        if self.inner.settings.sign {
+            let secret = secret.ok_or(Error::KeyNotFound)?;
+            request = request.header(SIGNATURE_HEADER, sign(secret, &delivery.body)?);
+        }
+
+        request.send().await?.error_for_status()?;
+        Ok(())
+    }
+
+    /// Attempt due deliveries to endpoints that are not backing off,
+    /// returning `true` if the queue has changed. The pass ends after
+    /// [`DELIVERY_PASS_LIMIT`], leaving remaining deliveries queued.
+    async fn deliver(
+        &self,
+        client: &reqwest::Client,
+        secret: Option<&[u8]>,
+        queue: &mut Queue,
+        endpoints: &mut AHashMap<String, Endpoint>,
+    ) -> bool {
+        let start = Instant::now();
+        let mut changed = false;
+
+        let mut deliveries = VecDeque::with_capacity(queue.deliveries.len());
+        while let Some(mut delivery) = queue.deliveries.pop_front() {
+            let now = unixtime_as_millis_f64() as u64;
+            let endpoint = endpoints.entry(delivery.url.clone()).or_default();
+            if delivery.next_attempt > now
+                || endpoint.next_attempt > now
+                || start.elapsed() >= DELIVERY_PASS_LIMIT
+            {
+                deliveries.push_back(delivery);
+                continue;
+            }
+
+            changed = true;
+            match self.post(client, secret, &delivery).await {
+                Ok(()) => {
+                    *endpoint = Endpoint::default();
+                }
+                Err(err) => {
+                    let now = unixtime_as_millis_f64() as u64;
+                    delivery.attempts += 1;
+                    let backoff = WebhookSettings::backoff(delivery.attempts);
+                    delivery.next_attempt = now + backoff.as_millis() as u64;
+                    endpoint.failures += 1;
+                    let backoff = WebhookSettings::backoff(endpoint.failures);
+                    endpoint.next_attempt = now + backoff.as_millis() as u64;
+                    log_error!(
+                        "Webhook: delivery to `{}` failed (attempt {}), retrying in {:?}: {err}",
+                        delivery.url,
+                        delivery.attempts,
+                        backoff
+                    );
+                    deliveries.push_back(delivery);
+                }
+            }
+        }
+        queue.deliveries = deliveries;
+
+        changed
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        let this = self.clone();
+        tokio::spawn(async move {
+            if let Err(error) = this.task().await {
+                log_error!("Webhook notifier task error: {:?}", error);
+            }
+        });
+
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        self.inner
+            .shutdown_ctl
+            .signal(())
+            .await
+            .expect("Webhook notifier shutdown signal error");
+        self.inner.events.close();
+
+        Ok(())
+    }
+
+    async fn task(self) -> Result<()> {
+        let events = self.inner.events.receiver.clone();
+        let shutdown_ctl_receiver = self.inner.shutdown_ctl.request.receiver.clone();
+        let shutdown_ctl_sender = self.inner.shutdown_ctl.response.sender.clone();
+
+        let settings = self.inner.settings;
+        let client = reqwest::Client::builder()
+            .timeout(WebhookSettings::timeout())
+            .build()?;
+        let secret = if settings.sign {
+            load_webhook_secret()
+                .map_err(|err| log_error!("Webhook: unable to load signing secret: {err}"))
+                .ok()
+        } else {
+            None
+        };
+
+        let mut queue = Queue::load(WebhookSettings::queue_file(), settings.queue_size);
+        let mut endpoints = AHashMap::new();
+        let mut delivery = interval(DELIVERY_INTERVAL);
+
+        loop {
+            select! {
+                msg = events.recv().fuse() => {
+                    match msg {
+                        Ok(event) => {
+                            let body = match serde_json::to_string(&WebhookPayload::from(&event)) {
+                                Ok(body) => body,
+                                Err(err) => {
+                                    log_error!("Webhook: unable to serialize alert event: {err}");
+                                    continue;
+                                }
+                            };
+                            let now = unixtime_as_millis_f64() as u64;
+                            for url in settings.urls.iter() {
+                                queue.push(Delivery {
+                                    url: url.clone(),
+                                    body: body.clone(),
+                                    attempts: 0,
+                                    next_attempt: now,
+                                });
+                            }
+                            queue.store();
+                        }
+                        Err(err) => {
+                            log_error!("Webhook: error while receiving alert events: {err}");
+                            break;
+                        }
+                    }
+                }
+
+                _ = delivery.next().fuse() => {
+                    if self.deliver(&client, secret.as_deref(), &mut queue, &mut endpoints).await {
+                        queue.store();
+                    }
+                }
+
+                _ = shutdown_ctl_receiver.recv().fuse() => {
+                    break;
+                },
+            }
+        }
+
+        queue.store();
+        shutdown_ctl_sender.send(()).await.unwrap();
+
+        Ok(())
+    }
+}
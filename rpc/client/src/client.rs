@@ -11,6 +11,33 @@ pub use workflow_rpc::client::{
 };
 pub use workflow_rpc::encoding::Encoding as WrpcEncoding;
 use workflow_serializer::prelude::Serializable;
+use workflow_websocket::client::{
+    Error as HandshakeError, Handshake, Message as WebSocketMessage, Result as HandshakeResult,
+};
+
+/// Sends the access token as the first message after connecting
+/// and waits for the server to respond with the granted role.
+struct TokenHandshake {
+    token: String,
+}
+
+#[async_trait]
+impl Handshake for TokenHandshake {
+    async fn handshake(
+        &self,
+        sender: &Sender<WebSocketMessage>,
+        receiver: &Receiver<WebSocketMessage>,
+    ) -> HandshakeResult<()> {
+        sender
+            .send(WebSocketMessage::Text(self.token.clone()))
+            .await
+            .map_err(|_| HandshakeError::NegotiationFailure)?;
+        match receiver.recv().await {
+            Ok(WebSocketMessage::Text(_role)) => Ok(()),
+            _ => Err(HandshakeError::NegotiationFailure),
+        }
+    }
+}
 
 struct Inner {
     rpc_client: Arc<RpcClient<RpcApiOps>>,
@@ -25,7 +52,7 @@ struct Inner {
 }
 
 impl Inner {
-    pub fn try_new(url: Option<&str>, encoding: Encoding) -> Result<Inner> {
+    pub fn try_new(url: Option<&str>, encoding: Encoding, token: Option<String>) -> Result<Inner> {
         let ctl_multiplexer = Multiplexer::<WrpcCtl>::new();
         let options = RpcClientOptions {
             url,
@@ -67,11 +94,16 @@ impl Inner {
             );
         });
 
+        let config = token.map(|token| WebSocketConfig {
+            handshake: Some(Arc::new(TokenHandshake { token })),
+            ..Default::default()
+        });
+
         let rpc = Arc::new(RpcClient::new_with_encoding(
             encoding,
             interface.into(),
             options,
-            None,
+            config,
         )?);
         let client = Self {
             rpc_client: rpc,
@@ -119,7 +151,21 @@ impl Default for MonitorRpcClient {
 
 impl MonitorRpcClient {
     pub fn try_new(url: Option<&str>, encoding: Option<Encoding>) -> Result<MonitorRpcClient> {
-        let inner = Arc::new(Inner::try_new(url, encoding.unwrap_or(Encoding::Borsh))?);
+        Self::try_new_with_token(url, encoding, None)
+    }
+
+    /// Create a client authenticating with the access `token`
+    /// when connecting to a server that requires authentication.
+    pub fn try_new_with_token(
+        url: Option<&str>,
+        encoding: Option<Encoding>,
+        token: Option<String>,
+    ) -> Result<MonitorRpcClient> {
+        let inner = Arc::new(Inner::try_new(
+            url,
+            encoding.unwrap_or(Encoding::Borsh),
+            token,
+        )?);
         let client = MonitorRpcClient { inner };
         Ok(client)
     }
@@ -318,7 +364,24 @@ impl MonitorRpcClient {
 }
 
 impl MonitorRpcClient {
-    build_wrpc_client_interface!(RpcApiOps, [Ping, GetStatus]);
+    build_wrpc_client_interface!(
+        RpcApiOps,
+        [
+            Ping,
+            GetStatus,
+            GetStatusHistory,
+            GetNodeTimeline,
+            GetSnapshot,
+            Subscribe,
+            Resolve,
+            ReconnectNode,
+            DisableNode,
+            EnableNode,
+            ForceConfigUpdate,
+            TriggerAbort,
+            GetUptime
+        ]
+    );
 
     pub async fn ping(&self) -> Result<PingResponse> {
         let request = PingRequest {};
@@ -329,4 +392,99 @@ impl MonitorRpcClient {
         let request = GetStatusRequest {};
         Ok(self.get_status_call(request).await?)
     }
+
+    pub async fn get_status_history(
+        &self,
+        uid: u64,
+        from: u64,
+        to: u64,
+        metrics: Vec<Metric>,
+        resolution: u64,
+    ) -> Result<GetStatusHistoryResponse> {
+        let request = GetStatusHistoryRequest {
+            uid,
+            from,
+            to,
+            metrics,
+            resolution,
+        };
+        Ok(self.get_status_history_call(request).await?)
+    }
+
+    pub async fn get_node_timeline(&self, uid: u64) -> Result<GetNodeTimelineResponse> {
+        let request = GetNodeTimelineRequest { uid };
+        Ok(self.get_node_timeline_call(request).await?)
+    }
+
+    pub async fn get_snapshot(&self) -> Result<GetSnapshotResponse> {
+        let request = GetSnapshotRequest {};
+        Ok(self.get_snapshot_call(request).await?)
+    }
+
+    /// Restrict notifications posted to this client. Empty lists match
+    /// everything; `min_interval` (msec) rate-limits `Status` updates
+    /// of each node.
+    pub async fn subscribe(
+        &self,
+        networks: Vec<NetworkId>,
+        services: Vec<String>,
+        uids: Vec<u64>,
+        update_kinds: Vec<UpdateKind>,
+        min_interval: u64,
+    ) -> Result<SubscribeResponse> {
+        let request = SubscribeRequest {
+            networks,
+            services,
+            uids,
+            update_kinds,
+            min_interval,
+        };
+        Ok(self.subscribe_call(request).await?)
+    }
+
+    pub async fn resolve(
+        &self,
+        service: String,
+        network_id: NetworkId,
+        tls: String,
+        encoding: String,
+    ) -> Result<ResolveResponse> {
+        let request = ResolveRequest {
+            service,
+            network_id,
+            tls,
+            encoding,
+        };
+        Ok(self.resolve_call(request).await?)
+    }
+
+    pub async fn reconnect_node(&self, uid: u64) -> Result<ReconnectNodeResponse> {
+        let request = ReconnectNodeRequest { uid };
+        Ok(self.reconnect_node_call(request).await?)
+    }
+
+    pub async fn disable_node(&self, uid: u64) -> Result<DisableNodeResponse> {
+        let request = DisableNodeRequest { uid };
+        Ok(self.disable_node_call(request).await?)
+    }
+
+    pub async fn enable_node(&self, uid: u64) -> Result<EnableNodeResponse> {
+        let request = EnableNodeRequest { uid };
+        Ok(self.enable_node_call(request).await?)
+    }
+
+    pub async fn force_config_update(&self) -> Result<ForceConfigUpdateResponse> {
+        let request = ForceConfigUpdateRequest {};
+        Ok(self.force_config_update_call(request).await?)
+    }
+
+    pub async fn trigger_abort(&self, uid: u64) -> Result<TriggerAbortResponse> {
+        let request = TriggerAbortRequest { uid };
+        Ok(self.trigger_abort_call(request).await?)
+    }
+
+    pub async fn get_uptime(&self) -> Result<GetUptimeResponse> {
+        let request = GetUptimeRequest {};
+        Ok(self.get_uptime_call(request).await?)
+    }
 }
@@ -15,7 +15,7 @@ pub use std::sync::{
     Arc, Mutex,
 };
 pub use workflow_core::{
-    channel::{Channel, DuplexChannel, Receiver},
+    channel::{Channel, DuplexChannel, Receiver, Sender},
     task::spawn,
 };
 pub use workflow_log::*;
@@ -8,4 +8,7 @@ pub mod prelude {
     pub use crate::message::*;
     pub use crate::ops::*;
     pub use crate::result::Result as RpcResult;
+    pub use kaspa_monitor_core::prelude::{
+        Divergence, DivergenceKind, Health, Metric, NodeInfo, RpcMethod,
+    };
 }
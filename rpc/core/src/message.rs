@@ -78,14 +78,967 @@ impl Deserializer for GetStatusResponse {
     }
 }
 
+/// Request stored status samples of the node `uid` within the `from..=to`
+/// time range (unix time in msec). Each returned sample carries the values
+/// of the requested `metrics`. If `resolution` (msec) is non-zero, samples
+/// are averaged into buckets of the given duration.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetStatusHistoryRequest {
+    pub uid: u64,
+    pub from: u64,
+    pub to: u64,
+    pub metrics: Vec<Metric>,
+    pub resolution: u64,
+}
+
+impl Serializer for GetStatusHistoryRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.uid, writer)?;
+        store!(u64, &self.from, writer)?;
+        store!(u64, &self.to, writer)?;
+        store!(Vec<Metric>, &self.metrics, writer)?;
+        store!(u64, &self.resolution, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetStatusHistoryRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let uid = load!(u64, reader)?;
+        let from = load!(u64, reader)?;
+        let to = load!(u64, reader)?;
+        let metrics = load!(Vec<Metric>, reader)?;
+        let resolution = load!(u64, reader)?;
+        Ok(Self {
+            uid,
+            from,
+            to,
+            metrics,
+            resolution,
+        })
+    }
+}
+
+/// Historical status sample; `values` correspond to the requested
+/// metrics and are `None` where the metric is not reported by the node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HistorySample {
+    pub timestamp: u64,
+    pub is_synced: bool,
+    pub values: Vec<Option<f64>>,
+}
+
+impl Serializer for HistorySample {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.timestamp, writer)?;
+        store!(bool, &self.is_synced, writer)?;
+        store!(Vec<Option<f64>>, &self.values, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for HistorySample {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let timestamp = load!(u64, reader)?;
+        let is_synced = load!(bool, reader)?;
+        let values = load!(Vec<Option<f64>>, reader)?;
+        Ok(Self {
+            timestamp,
+            is_synced,
+            values,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetStatusHistoryResponse {
+    pub uid: u64,
+    pub metrics: Vec<Metric>,
+    pub samples: Vec<HistorySample>,
+}
+
+impl Serializer for GetStatusHistoryResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.uid, writer)?;
+        store!(Vec<Metric>, &self.metrics, writer)?;
+        serialize!(Vec<HistorySample>, &self.samples, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetStatusHistoryResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let uid = load!(u64, reader)?;
+        let metrics = load!(Vec<Metric>, reader)?;
+        let samples = deserialize!(Vec<HistorySample>, reader)?;
+        Ok(Self {
+            uid,
+            metrics,
+            samples,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetNodeTimelineRequest {
+    pub uid: u64,
+}
+
+impl Serializer for GetNodeTimelineRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.uid, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetNodeTimelineRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let uid = load!(u64, reader)?;
+        Ok(Self { uid })
+    }
+}
+
+#[derive(
+    Describe,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+#[borsh(use_discriminant = true)]
+pub enum TimelineState {
+    /// No samples were recorded (node offline or monitor not running)
+    Offline = 0,
+    Syncing,
+    Synced,
+}
+
+/// Continuous period of time (`from..to`, unix time in msec)
+/// during which the node remained in the same state.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TimelineSegment {
+    pub from: u64,
+    pub to: u64,
+    pub state: TimelineState,
+}
+
+impl Serializer for TimelineSegment {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.from, writer)?;
+        store!(u64, &self.to, writer)?;
+        store!(TimelineState, &self.state, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for TimelineSegment {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let from = load!(u64, reader)?;
+        let to = load!(u64, reader)?;
+        let state = load!(TimelineState, reader)?;
+        Ok(Self { from, to, state })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetNodeTimelineResponse {
+    pub uid: u64,
+    pub segments: Vec<TimelineSegment>,
+}
+
+impl Serializer for GetNodeTimelineResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.uid, writer)?;
+        serialize!(Vec<TimelineSegment>, &self.segments, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetNodeTimelineResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let uid = load!(u64, reader)?;
+        let segments = deserialize!(Vec<TimelineSegment>, reader)?;
+        Ok(Self { uid, segments })
+    }
+}
+
+/// State of a single node connection.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConnectionSnapshot {
+    pub node: Arc<NodeInfo>,
+    // system id of the node (0 if caps were not received)
+    pub sid: u64,
+    pub connected: bool,
+    pub online: bool,
+    pub synced: bool,
+    // connection delegates to another connection of the same node
+    pub delegator: bool,
+    // uids of the delegate chain, nearest delegate first
+    pub delegates: Vec<u64>,
+    pub caps: Option<Arc<Caps>>,
+    pub status: Option<Arc<Status>>,
+    // unix time in msec at which the connection entered its current state
+    pub since: u64,
+    pub health: Option<Arc<Health>>,
+}
+
+impl Serializer for ConnectionSnapshot {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &3, writer)?;
+        serialize!(NodeInfo, &self.node, writer)?;
+        store!(u64, &self.sid, writer)?;
+        store!(bool, &self.connected, writer)?;
+        store!(bool, &self.online, writer)?;
+        store!(bool, &self.synced, writer)?;
+        store!(bool, &self.delegator, writer)?;
+        store!(Vec<u64>, &self.delegates, writer)?;
+        store!(bool, &self.caps.is_some(), writer)?;
+        if let Some(caps) = &self.caps {
+            serialize!(Caps, caps, writer)?;
+        }
+        store!(bool, &self.status.is_some(), writer)?;
+        if let Some(status) = &self.status {
+            serialize!(Status, status, writer)?;
+        }
+        store!(u64, &self.since, writer)?;
+        store!(bool, &self.health.is_some(), writer)?;
+        if let Some(health) = &self.health {
+            serialize!(Health, health, writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl Deserializer for ConnectionSnapshot {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let version = load!(u16, reader)?;
+        let node = Arc::new(deserialize!(NodeInfo, reader)?);
+        let sid = load!(u64, reader)?;
+        let connected = load!(bool, reader)?;
+        let online = load!(bool, reader)?;
+        let synced = load!(bool, reader)?;
+        let delegator = load!(bool, reader)?;
+        let delegates = load!(Vec<u64>, reader)?;
+        let caps = if load!(bool, reader)? {
+            Some(Arc::new(deserialize!(Caps, reader)?))
+        } else {
+            None
+        };
+        let status = if load!(bool, reader)? {
+            Some(Arc::new(deserialize!(Status, reader)?))
+        } else {
+            None
+        };
+        let since = if version > 1 { load!(u64, reader)? } else { 0 };
+        let health = if version > 2 && load!(bool, reader)? {
+            Some(Arc::new(deserialize!(Health, reader)?))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            node,
+            sid,
+            connected,
+            online,
+            synced,
+            delegator,
+            delegates,
+            caps,
+            status,
+            since,
+            health,
+        })
+    }
+}
+
+/// Current state of all node connections.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub connections: Vec<ConnectionSnapshot>,
+}
+
+impl Serializer for Snapshot {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<ConnectionSnapshot>, &self.connections, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for Snapshot {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let connections = deserialize!(Vec<ConnectionSnapshot>, reader)?;
+        Ok(Self { connections })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetSnapshotRequest {}
+
+impl Serializer for GetSnapshotRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetSnapshotRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetSnapshotResponse {
+    pub snapshot: Arc<Snapshot>,
+}
+
+impl Serializer for GetSnapshotResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Snapshot, &self.snapshot, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetSnapshotResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let snapshot = Arc::new(deserialize!(Snapshot, reader)?);
+        Ok(Self { snapshot })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SubscribeRequest {
+    // networks, services (kaspa, sparkle), node uids and update kinds
+    // to receive notifications for; an empty list matches everything
+    pub networks: Vec<NetworkId>,
+    pub services: Vec<String>,
+    pub uids: Vec<u64>,
+    pub update_kinds: Vec<UpdateKind>,
+    // minimum interval in msec between `Status` updates of the same node
+    pub min_interval: u64,
+}
+
+impl Serializer for SubscribeRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<NetworkId>, &self.networks, writer)?;
+        store!(Vec<String>, &self.services, writer)?;
+        store!(Vec<u64>, &self.uids, writer)?;
+        store!(Vec<UpdateKind>, &self.update_kinds, writer)?;
+        store!(u64, &self.min_interval, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for SubscribeRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let networks = load!(Vec<NetworkId>, reader)?;
+        let services = load!(Vec<String>, reader)?;
+        let uids = load!(Vec<u64>, reader)?;
+        let update_kinds = load!(Vec<UpdateKind>, reader)?;
+        let min_interval = load!(u64, reader)?;
+        Ok(Self {
+            networks,
+            services,
+            uids,
+            update_kinds,
+            min_interval,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SubscribeResponse {}
+
+impl Serializer for SubscribeResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for SubscribeResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// Request the least loaded available node of the `service` (kaspa,
+/// sparkle) on `network_id`. `tls` is one of `tls`, `none` or `any`;
+/// `encoding` is `borsh` or `json` (wRPC) or `protobuf` (gRPC).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ResolveRequest {
+    pub service: String,
+    pub network_id: NetworkId,
+    pub tls: String,
+    pub encoding: String,
+}
+
+impl Serializer for ResolveRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.service, writer)?;
+        store!(NetworkId, &self.network_id, writer)?;
+        store!(String, &self.tls, writer)?;
+        store!(String, &self.encoding, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for ResolveRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let service = load!(String, reader)?;
+        let network_id = load!(NetworkId, reader)?;
+        let tls = load!(String, reader)?;
+        let encoding = load!(String, reader)?;
+        Ok(Self {
+            service,
+            network_id,
+            tls,
+            encoding,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ResolveResponse {
+    pub uid: u64,
+    pub url: String,
+}
+
+impl Serializer for ResolveResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.uid, writer)?;
+        store!(String, &self.url, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for ResolveResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let uid = load!(u64, reader)?;
+        let url = load!(String, reader)?;
+        Ok(Self { uid, url })
+    }
+}
+
+/// Disconnect and reconnect the node `uid` (admin).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReconnectNodeRequest {
+    pub uid: u64,
+}
+
+impl Serializer for ReconnectNodeRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.uid, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for ReconnectNodeRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let uid = load!(u64, reader)?;
+        Ok(Self { uid })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReconnectNodeResponse {}
+
+impl Serializer for ReconnectNodeResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for ReconnectNodeResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// Disconnect the node `uid` until it is re-enabled or the monitor
+/// is restarted, regardless of the node config (admin).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DisableNodeRequest {
+    pub uid: u64,
+}
+
+impl Serializer for DisableNodeRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.uid, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for DisableNodeRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let uid = load!(u64, reader)?;
+        Ok(Self { uid })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DisableNodeResponse {}
+
+impl Serializer for DisableNodeResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for DisableNodeResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// Revert [`DisableNodeRequest`] for the node `uid` (admin).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnableNodeRequest {
+    pub uid: u64,
+}
+
+impl Serializer for EnableNodeRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.uid, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for EnableNodeRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let uid = load!(u64, reader)?;
+        Ok(Self { uid })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnableNodeResponse {}
+
+impl Serializer for EnableNodeResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for EnableNodeResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// Reload the node config without waiting for the next update (admin).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ForceConfigUpdateRequest {}
+
+impl Serializer for ForceConfigUpdateRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for ForceConfigUpdateRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ForceConfigUpdateResponse {}
+
+impl Serializer for ForceConfigUpdateResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for ForceConfigUpdateResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// Abort the RPC connection of the node `uid` to
+/// exercise the reconnection sequence (admin).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TriggerAbortRequest {
+    pub uid: u64,
+}
+
+impl Serializer for TriggerAbortRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.uid, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for TriggerAbortRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let uid = load!(u64, reader)?;
+        Ok(Self { uid })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TriggerAbortResponse {}
+
+impl Serializer for TriggerAbortResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for TriggerAbortResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetUptimeRequest {}
+
+impl Serializer for GetUptimeRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetUptimeRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+#[derive(
+    Describe,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+#[borsh(use_discriminant = true)]
+pub enum OutageCause {
+    /// RPC connection to the node was lost
+    Disconnect = 0,
+    /// Node is connected but its status can not be obtained
+    MetricsError,
+    /// Node is responsive but not synced
+    NotSynced,
+}
+
+impl std::fmt::Display for OutageCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            OutageCause::Disconnect => "disconnect",
+            OutageCause::MetricsError => "metrics-error",
+            OutageCause::NotSynced => "not-synced",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Period (unix time in msec) during which a node or machine was unavailable.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Outage {
+    pub start: u64,
+    // `None` if the outage is ongoing
+    pub end: Option<u64>,
+    // msec, up to the time of the report for ongoing outages
+    pub duration: u64,
+    pub cause: OutageCause,
+}
+
+impl Serializer for Outage {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.start, writer)?;
+        store!(Option<u64>, &self.end, writer)?;
+        store!(u64, &self.duration, writer)?;
+        store!(OutageCause, &self.cause, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for Outage {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let start = load!(u64, reader)?;
+        let end = load!(Option<u64>, reader)?;
+        let duration = load!(u64, reader)?;
+        let cause = load!(OutageCause, reader)?;
+        Ok(Self {
+            start,
+            end,
+            duration,
+            cause,
+        })
+    }
+}
+
+/// Availability of a node (by uid) or a machine (by sid). Uptime
+/// percentages cover the time the node or machine was monitored
+/// within the last 24 hours, 7 days and 30 days (`None` if it was
+/// not monitored during the period).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Availability {
+    pub id: u64,
+    // node address, empty for machines
+    pub name: String,
+    pub uptime_24h: Option<f64>,
+    pub uptime_7d: Option<f64>,
+    pub uptime_30d: Option<f64>,
+    // outages within the last 30 days, oldest first
+    pub outages: Vec<Outage>,
+}
+
+impl Serializer for Availability {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.id, writer)?;
+        store!(String, &self.name, writer)?;
+        store!(Option<f64>, &self.uptime_24h, writer)?;
+        store!(Option<f64>, &self.uptime_7d, writer)?;
+        store!(Option<f64>, &self.uptime_30d, writer)?;
+        serialize!(Vec<Outage>, &self.outages, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for Availability {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let id = load!(u64, reader)?;
+        let name = load!(String, reader)?;
+        let uptime_24h = load!(Option<f64>, reader)?;
+        let uptime_7d = load!(Option<f64>, reader)?;
+        let uptime_30d = load!(Option<f64>, reader)?;
+        let outages = deserialize!(Vec<Outage>, reader)?;
+        Ok(Self {
+            id,
+            name,
+            uptime_24h,
+            uptime_7d,
+            uptime_30d,
+            outages,
+        })
+    }
+}
+
+/// Availability of all monitored nodes and machines.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct UptimeReport {
+    // unix time in msec at which the report was created
+    pub timestamp: u64,
+    pub nodes: Vec<Availability>,
+    pub machines: Vec<Availability>,
+}
+
+impl Serializer for UptimeReport {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.timestamp, writer)?;
+        serialize!(Vec<Availability>, &self.nodes, writer)?;
+        serialize!(Vec<Availability>, &self.machines, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for UptimeReport {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let timestamp = load!(u64, reader)?;
+        let nodes = deserialize!(Vec<Availability>, reader)?;
+        let machines = deserialize!(Vec<Availability>, reader)?;
+        Ok(Self {
+            timestamp,
+            nodes,
+            machines,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetUptimeResponse {
+    pub report: Arc<UptimeReport>,
+}
+
+impl Serializer for GetUptimeResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(UptimeReport, &self.report, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetUptimeResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let report = Arc::new(deserialize!(UptimeReport, reader)?);
+        Ok(Self { report })
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+#[derive(
+    Describe,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+#[borsh(use_discriminant = true)]
+pub enum UpdateKind {
+    Status = 0,
+    Caps,
+    Divergence,
+    Convergence,
+    NodeAdded,
+    NodeRemoved,
+    MachineRemoved,
+    State,
+    Health,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Update {
-    Status { status: Arc<Status> },
-    Caps { uid: u64, caps: Arc<Caps> },
+    Status {
+        status: Arc<Status>,
+    },
+    Caps {
+        uid: u64,
+        caps: Arc<Caps>,
+    },
+    Divergence {
+        divergence: Arc<Divergence>,
+    },
+    Convergence {
+        uid: u64,
+        kind: DivergenceKind,
+    },
+    NodeAdded {
+        node: Arc<NodeInfo>,
+    },
+    NodeRemoved {
+        uid: u64,
+    },
+    MachineRemoved {
+        sid: u64,
+    },
+    State {
+        uid: u64,
+        connected: bool,
+        online: bool,
+        synced: bool,
+        // uid of the connection this connection delegates to
+        delegate: Option<u64>,
+        // unix time in msec at which the state was entered
+        since: u64,
+    },
+    Health {
+        health: Arc<Health>,
+    },
+}
+
+impl Update {
+    pub fn kind(&self) -> UpdateKind {
+        match self {
+            Update::Status { .. } => UpdateKind::Status,
+            Update::Caps { .. } => UpdateKind::Caps,
+            Update::Divergence { .. } => UpdateKind::Divergence,
+            Update::Convergence { .. } => UpdateKind::Convergence,
+            Update::NodeAdded { .. } => UpdateKind::NodeAdded,
+            Update::NodeRemoved { .. } => UpdateKind::NodeRemoved,
+            Update::MachineRemoved { .. } => UpdateKind::MachineRemoved,
+            Update::State { .. } => UpdateKind::State,
+            Update::Health { .. } => UpdateKind::Health,
+        }
+    }
+
+    /// Uid of the node the update refers to (`None` for machine updates).
+    pub fn uid(&self) -> Option<u64> {
+        match self {
+            Update::Status { status } => Some(status.uid()),
+            Update::Caps { uid, .. } => Some(*uid),
+            Update::Divergence { divergence } => Some(divergence.uid),
+            Update::Convergence { uid, .. } => Some(*uid),
+            Update::NodeAdded { node } => Some(node.uid),
+            Update::NodeRemoved { uid } => Some(*uid),
+            Update::MachineRemoved { .. } => None,
+            Update::State { uid, .. } => Some(*uid),
+            Update::Health { health } => Some(health.uid),
+        }
+    }
 }
 
 impl Serializer for Update {
@@ -101,6 +1054,47 @@ impl Serializer for Update {
                 store!(u64, &uid, writer)?;
                 serialize!(Caps, &caps, writer)?;
             }
+            Update::Divergence { divergence } => {
+                store!(u8, &2, writer)?;
+                serialize!(Divergence, &divergence, writer)?;
+            }
+            Update::Convergence { uid, kind } => {
+                store!(u8, &3, writer)?;
+                store!(u64, &uid, writer)?;
+                store!(DivergenceKind, &kind, writer)?;
+            }
+            Update::NodeAdded { node } => {
+                store!(u8, &5, writer)?;
+                serialize!(NodeInfo, &node, writer)?;
+            }
+            Update::NodeRemoved { uid } => {
+                store!(u8, &4, writer)?;
+                store!(u64, &uid, writer)?;
+            }
+            Update::MachineRemoved { sid } => {
+                store!(u8, &6, writer)?;
+                store!(u64, &sid, writer)?;
+            }
+            Update::State {
+                uid,
+                connected,
+                online,
+                synced,
+                delegate,
+                since,
+            } => {
+                store!(u8, &7, writer)?;
+                store!(u64, &uid, writer)?;
+                store!(bool, &connected, writer)?;
+                store!(bool, &online, writer)?;
+                store!(bool, &synced, writer)?;
+                store!(Option<u64>, &delegate, writer)?;
+                store!(u64, &since, writer)?;
+            }
+            Update::Health { health } => {
+                store!(u8, &8, writer)?;
+                serialize!(Health, &health, writer)?;
+            }
         }
         Ok(())
     }
@@ -125,6 +1119,53 @@ impl Deserializer for Update {
                     caps: Arc::new(caps),
                 })
             }
+            2 => {
+                let divergence = deserialize!(Divergence, reader)?;
+                Ok(Update::Divergence {
+                    divergence: Arc::new(divergence),
+                })
+            }
+            3 => {
+                let uid = load!(u64, reader)?;
+                let kind = load!(DivergenceKind, reader)?;
+                Ok(Update::Convergence { uid, kind })
+            }
+            4 => {
+                let uid = load!(u64, reader)?;
+                Ok(Update::NodeRemoved { uid })
+            }
+            5 => {
+                let node = deserialize!(NodeInfo, reader)?;
+                Ok(Update::NodeAdded {
+                    node: Arc::new(node),
+                })
+            }
+            6 => {
+                let sid = load!(u64, reader)?;
+                Ok(Update::MachineRemoved { sid })
+            }
+            7 => {
+                let uid = load!(u64, reader)?;
+                let connected = load!(bool, reader)?;
+                let online = load!(bool, reader)?;
+                let synced = load!(bool, reader)?;
+                let delegate = load!(Option<u64>, reader)?;
+                let since = load!(u64, reader)?;
+                Ok(Update::State {
+                    uid,
+                    connected,
+                    online,
+                    synced,
+                    delegate,
+                    since,
+                })
+            }
+            8 => {
+                let health = deserialize!(Health, reader)?;
+                Ok(Update::Health {
+                    health: Arc::new(health),
+                })
+            }
             _ => Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
                 "Invalid update type",
@@ -136,6 +1177,7 @@ impl Deserializer for Update {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Notification {
     Update { update: Update },
+    Snapshot { snapshot: Arc<Snapshot> },
 }
 
 impl Serializer for Notification {
@@ -146,6 +1188,10 @@ impl Serializer for Notification {
                 store!(u8, &0, writer)?;
                 serialize!(Update, &update, writer)?;
             }
+            Notification::Snapshot { snapshot } => {
+                store!(u8, &1, writer)?;
+                serialize!(Snapshot, &snapshot, writer)?;
+            }
         }
         Ok(())
     }
@@ -160,6 +1206,12 @@ impl Deserializer for Notification {
                 let update = deserialize!(Update, reader)?;
                 Ok(Notification::Update { update })
             }
+            1 => {
+                let snapshot = deserialize!(Snapshot, reader)?;
+                Ok(Notification::Snapshot {
+                    snapshot: Arc::new(snapshot),
+                })
+            }
             _ => Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
                 "Invalid notification type",
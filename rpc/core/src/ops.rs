@@ -18,4 +18,15 @@ pub enum RpcApiOps {
     Notify = 0,
     Ping,
     GetStatus,
+    GetStatusHistory,
+    GetNodeTimeline,
+    GetSnapshot,
+    Subscribe,
+    Resolve,
+    ReconnectNode,
+    DisableNode,
+    EnableNode,
+    ForceConfigUpdate,
+    TriggerAbort,
+    GetUptime,
 }
@@ -1,4 +1,5 @@
 use crate::imports::*;
+use kaspa_monitor_nexus::auth::Role;
 use kaspa_monitor_nexus::context::ContextT;
 use kaspa_monitor_nexus::result::Result as NexusResult;
 use std::fmt;
@@ -7,6 +8,9 @@ use std::fmt;
 struct ConnectionInner {
     pub id: u64,
     pub peer: SocketAddr,
+    pub role: Role,
+    // name of the access token used to authenticate
+    pub name: Option<String>,
     pub messenger: Arc<Messenger>,
 }
 
@@ -29,11 +33,19 @@ pub struct Connection {
 }
 
 impl Connection {
-    pub fn new(id: u64, peer: &SocketAddr, messenger: Arc<Messenger>) -> Connection {
+    pub fn new(
+        id: u64,
+        peer: &SocketAddr,
+        role: Role,
+        name: Option<String>,
+        messenger: Arc<Messenger>,
+    ) -> Connection {
         Connection {
             inner: Arc::new(ConnectionInner {
                 id,
                 peer: *peer,
+                role,
+                name,
                 messenger,
             }),
         }
@@ -53,6 +65,10 @@ impl Connection {
         &self.inner.peer
     }
 
+    pub fn role(&self) -> Role {
+        self.inner.role
+    }
+
     /// Creates a WebSocket [`Message`] that can be posted to the connection ([`Messenger`]) sink
     /// directly.
     pub fn create_serialized_notification_message<Ops, Msg>(
@@ -81,7 +97,11 @@ impl Connection {
 
 impl fmt::Display for Connection {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}@{}", self.inner.id, self.inner.peer)
+        write!(f, "{}@{}", self.inner.id, self.inner.peer)?;
+        if let Some(name) = self.inner.name.as_ref() {
+            write!(f, " [{name}]")?;
+        }
+        Ok(())
     }
 }
 
@@ -91,6 +111,10 @@ impl ContextT for Connection {
         self.inner.id
     }
 
+    fn role(&self) -> Role {
+        self.inner.role
+    }
+
     async fn notify(&self, notification: Notification) -> NexusResult<()> {
         let message = Connection::create_serialized_notification_message(
             self.messenger().encoding(),
@@ -100,4 +124,9 @@ impl ContextT for Connection {
         self.inner.messenger.send_raw_message(message)?;
         Ok(())
     }
+
+    fn disconnect(&self) -> NexusResult<()> {
+        self.inner.messenger.close()?;
+        Ok(())
+    }
 }
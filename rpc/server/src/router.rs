@@ -17,7 +17,21 @@ impl Router {
             Server,
             Connection,
             RpcApiOps,
-            [Ping, GetStatus]
+            [
+                Ping,
+                GetStatus,
+                GetStatusHistory,
+                GetNodeTimeline,
+                GetSnapshot,
+                Subscribe,
+                Resolve,
+                ReconnectNode,
+                DisableNode,
+                EnableNode,
+                ForceConfigUpdate,
+                TriggerAbort,
+                GetUptime
+            ]
         );
 
         Router {
@@ -1,5 +1,8 @@
 use crate::{connection::Connection, service::WrpcOptions};
 use async_trait::async_trait;
+use futures::{SinkExt, StreamExt};
+use kaspa_monitor_nexus::auth::{Auth, Role, Token};
+use kaspa_monitor_nexus::config::AuthSettings;
 use kaspa_monitor_nexus::prelude::Nexus;
 use kaspa_monitor_rpc_core::ops::RpcApiOps;
 use std::{
@@ -9,11 +12,14 @@ use std::{
         Arc, Mutex,
     },
 };
+use workflow_log::prelude::*;
 use workflow_rpc::server::prelude::*;
 
 struct Inner {
     pub nexus: Nexus,
     pub options: Arc<WrpcOptions>,
+    // access tokens (`None` if authentication is disabled)
+    pub auth: Option<Auth>,
     pub next_connection_id: AtomicU64,
     pub sockets: Mutex<HashMap<u64, Connection>>,
 }
@@ -30,13 +36,25 @@ impl RpcHandler for Server {
     async fn handshake(
         self: Arc<Self>,
         peer: &SocketAddr,
-        _sender: &mut WebSocketSender,
-        _receiver: &mut WebSocketReceiver,
+        sender: &mut WebSocketSender,
+        receiver: &mut WebSocketReceiver,
         messenger: Arc<Messenger>,
     ) -> WebSocketResult<Connection> {
         println!("WebSocket connected: {}", peer);
+        let (role, name) = match self.inner.auth.as_ref() {
+            Some(auth) => match Self::authenticate(auth, sender, receiver).await {
+                Ok(token) => (token.role, token.name.clone()),
+                Err(err) => {
+                    log_warn!("wRPC: rejecting {peer}: {err}");
+                    sender.send(Message::Close(None)).await.ok();
+                    return Err(err.into());
+                }
+            },
+            None => (Role::ReadOnly, None),
+        };
+
         let id = self.inner.next_connection_id.fetch_add(1, Ordering::SeqCst);
-        let connection = Connection::new(id, peer, messenger);
+        let connection = Connection::new(id, peer, role, name, messenger);
         self.inner
             .sockets
             .lock()
@@ -56,11 +74,12 @@ impl RpcHandler for Server {
 }
 
 impl Server {
-    pub fn new(nexus: &Nexus, options: Arc<WrpcOptions>) -> Self {
+    pub fn new(nexus: &Nexus, options: Arc<WrpcOptions>, auth: Option<Auth>) -> Self {
         Server {
             inner: Arc::new(Inner {
                 nexus: nexus.clone(),
                 options,
+                auth,
                 next_connection_id: AtomicU64::new(0),
                 sockets: Mutex::new(HashMap::new()),
             }),
@@ -78,4 +97,34 @@ impl Server {
     pub fn verbose(&self) -> bool {
         self.inner.options.verbose
     }
+
+    /// Read the access token sent by the client as the first message and
+    /// respond with the granted role.
+    async fn authenticate<'auth>(
+        auth: &'auth Auth,
+        sender: &mut WebSocketSender,
+        receiver: &mut WebSocketReceiver,
+    ) -> std::result::Result<&'auth Token, String> {
+        let message = tokio::time::timeout(AuthSettings::handshake_timeout(), receiver.next())
+            .await
+            .map_err(|_| "handshake timeout".to_string())?;
+
+        let token = match message {
+            Some(Ok(Message::Text(token))) => token,
+            Some(Err(err)) => return Err(err.to_string()),
+            _ => return Err("expected access token".to_string()),
+        };
+
+        let token = auth
+            .authenticate(token.trim())
+            .ok_or_else(|| "invalid access token".to_string())?;
+        log_info!("wRPC: authenticated {token}");
+
+        sender
+            .send(Message::Text(token.role.to_string().into()))
+            .await
+            .map_err(|err| err.to_string())?;
+
+        Ok(token)
+    }
 }
@@ -2,6 +2,8 @@ use crate::imports::*;
 use crate::router::Router;
 use crate::{connection::*, server::*};
 use kaspa_monitor_core::runtime::*;
+use kaspa_monitor_nexus::auth::Auth;
+use kaspa_monitor_nexus::config::AuthSettings;
 use kaspa_monitor_nexus::prelude::Nexus;
 use kaspa_monitor_rpc_core::prelude::*;
 pub use workflow_rpc::server::{Encoding as WrpcEncoding, WebSocketConfig, WebSocketCounters};
@@ -42,7 +44,8 @@ impl WrpcService {
     /// Create and initialize RpcServer
     pub async fn try_new(nexus: &Nexus, options: WrpcOptions) -> Result<Self> {
         let options = Arc::new(options);
-        let server = Server::new(nexus, options.clone());
+        let auth = AuthSettings::enable().then(Auth::try_new).transpose()?;
+        let server = Server::new(nexus, options.clone(), auth);
 
         // Create router (initializes Interface registering RPC method and notification handlers)
         let router = Arc::new(Router::new(server.clone()));
@@ -1,4 +1,5 @@
 use kaspa_utils::networking::ContextualNetAddress;
+use std::path::PathBuf;
 
 #[derive(Debug)]
 pub struct Args {
@@ -6,12 +7,16 @@ pub struct Args {
     pub trace: bool,
     pub debug: bool,
     pub rpc_listen: ContextualNetAddress,
+    pub http: bool,
+    pub http_listen: ContextualNetAddress,
+    pub node_rpc: Vec<String>,
+    pub config: Option<PathBuf>,
 }
 
 impl Args {
     pub fn parse() -> Args {
         #[allow(unused)]
-        use clap::{arg, command, Arg, Command};
+        use clap::{arg, command, Arg, ArgAction, Command};
 
         let cmd = Command::new("sparkled")
             .about(format!(
@@ -36,13 +41,36 @@ impl Args {
                         "Interface:port to listen for wRPC connections (default: 127.0.0.1:6969).",
                     ),
             )
+            .arg(
+                Arg::new("http-listen")
+                    .long("http-listen")
+                    .value_name("ip[:port]")
+                    .num_args(0..=1)
+                    .require_equals(true)
+                    .value_parser(clap::value_parser!(ContextualNetAddress))
+                    .help(
+                        "Interface:port to listen for HTTP connections (default: 127.0.0.1:8080).",
+                    ),
+            )
             .arg(
                 Arg::new("node-rpc")
                     .long("node-rpc")
-                    .value_name("ws://address[:port] or wss://address[:port]")
-                    .num_args(0..=1)
+                    .value_name("ws://address[:port][#network], wss://address[:port][#network] or grpc://address[:port][#network]")
+                    .num_args(1)
                     .require_equals(true)
-                    .help("wRPC URL of the node (disables resolver)."),
+                    .value_delimiter(',')
+                    .action(ArgAction::Append)
+                    .help("RPC URL of a node to monitor, can be repeated (disables resolver). The network is queried from the node unless given as URL fragment."),
+            )
+            .arg(
+                Arg::new("config")
+                    .long("config")
+                    .value_name("path")
+                    .num_args(1)
+                    .require_equals(true)
+                    .conflicts_with("node-rpc")
+                    .value_parser(clap::value_parser!(PathBuf))
+                    .help("Unencrypted node config file, reloaded on change (disables resolver)."),
             );
 
         let matches = cmd.get_matches();
@@ -51,11 +79,25 @@ impl Args {
         let debug = matches.get_one::<bool>("debug").cloned().unwrap_or(false);
         let verbose = matches.get_one::<bool>("verbose").cloned().unwrap_or(false);
 
+        let http = matches.get_one::<bool>("http").cloned().unwrap_or(false);
+
+        let http_listen = matches
+            .get_one::<ContextualNetAddress>("http-listen")
+            .cloned()
+            .unwrap_or("127.0.0.1:8080".parse().unwrap());
+
         let rpc_listen = matches
             .get_one::<ContextualNetAddress>("rpc-listen")
             .cloned()
             .unwrap_or("127.0.0.1:6969".parse().unwrap());
 
+        let node_rpc = matches
+            .get_many::<String>("node-rpc")
+            .map(|urls| urls.cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        let config = matches.get_one::<PathBuf>("config").cloned();
+
         if matches.get_one::<bool>("version").cloned().unwrap_or(false) {
             println!("v{}-{}", crate::VERSION, crate::GIT_DESCRIBE);
             std::process::exit(0);
@@ -65,6 +107,10 @@ impl Args {
                 debug,
                 verbose,
                 rpc_listen,
+                http,
+                http_listen,
+                node_rpc,
+                config,
             }
         }
     }
@@ -82,6 +128,8 @@ impl From<&Args> for kaspa_monitor_nexus::args::Args {
             verbose: args.verbose,
             trace: args.trace,
             debug: args.debug,
+            node_rpc: args.node_rpc.clone(),
+            config: args.config.clone(),
         }
     }
 }
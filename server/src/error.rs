@@ -17,8 +17,9 @@ pub enum Error {
     #[error(transparent)]
     Nexus(#[from] kaspa_monitor_nexus::error::Error),
 
-    // #[error(transparent)]
-    // Http(#[from] kaspa_monitor_http_server::error::Error),
+    #[error(transparent)]
+    Http(#[from] kaspa_monitor_http_server::error::Error),
+
     #[error(transparent)]
     RpcCore(#[from] kaspa_monitor_rpc_core::error::Error),
 
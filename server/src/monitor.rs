@@ -1,4 +1,5 @@
 use kaspa_monitor_core::runtime::Runtime;
+use kaspa_monitor_http_server::{HttpOptions, HttpService};
 use kaspa_monitor_nexus::prelude::Nexus;
 use kaspa_monitor_rpc_server::{WrpcOptions, WrpcService};
 use std::sync::Arc;
@@ -33,6 +34,13 @@ impl Server {
             .expect("Unable to create wRPC service.");
         runtime.bind(Arc::new(wrpc_server));
 
+        if args.http {
+            let http_options = HttpOptions::default().listen(args.http_listen.to_string().as_str());
+            let http_server =
+                HttpService::try_new(&nexus, http_options).expect("Unable to create HTTP service.");
+            runtime.bind(Arc::new(http_server));
+        }
+
         runtime.run().await?;
 
         Ok(())